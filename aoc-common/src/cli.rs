@@ -0,0 +1,51 @@
+use std::path::Path;
+
+use crate::{input, logger, Solution};
+
+/// Entry point of the standalone per-day binaries:
+/// `dayNN [-q|-v|-vv] <filename> 1|2`, where a filename of `-` reads the
+/// input from stdin.
+pub fn day_main<S: Solution>() {
+    let (flags, args): (Vec<String>, Vec<String>) =
+        std::env::args().partition(|arg| arg.starts_with('-') && arg != "-");
+    let (mut verbose, mut quiet, mut valid) = (0, false, true);
+    for flag in &flags {
+        match flag.as_str() {
+            "-q" => quiet = true,
+            "-v" => verbose = 1,
+            "-vv" => verbose = 2,
+            _ => valid = false,
+        }
+    }
+    if !valid || args.len() != 3 || (args[2] != "1" && args[2] != "2") {
+        eprintln!("Usage: {} [-q|-v|-vv] <filename> 1|2", args[0]);
+        return;
+    }
+    logger::init(logger::level(verbose, quiet));
+    let input = match input::read(Path::new(&args[1])) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Failed to read {}: {}", args[1], err);
+            std::process::exit(1);
+        }
+    };
+    let puzzle = match S::parse(&input) {
+        Ok(puzzle) => puzzle,
+        Err(err) => {
+            eprint!("{}", err.diagnostic(&input));
+            std::process::exit(1);
+        }
+    };
+    let answer = if args[2] == "1" {
+        puzzle.part1().map(|answer| answer.to_string())
+    } else {
+        puzzle.part2().map(|answer| answer.to_string())
+    };
+    match answer {
+        Ok(answer) => println!("Part {}: {}", args[2], answer),
+        Err(err) => {
+            eprintln!("Part {}: {}", args[2], err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,505 @@
+//! 2D geometry shared by the grid puzzles.
+//!
+//! The coordinate system follows the puzzle inputs: `x` grows to the east
+//! (along a line) and `y` grows to the south (line by line).
+
+use std::{
+    fmt::Display,
+    ops::{Add, Index, IndexMut, Mul, Neg, Sub},
+};
+
+use crate::ParseError;
+
+/// A position or offset on the plane, signed or unsigned.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Distance walking only along the axes.
+    pub fn manhattan(&self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Distance walking along the axes and diagonals, i.e. the number of king
+    /// moves between the two points.
+    pub fn chebyshev(&self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + TryInto<i64> + TryFrom<i64>,
+{
+    /// The adjacent point in `dir`, `None` when it does not fit in `T`
+    /// (e.g. stepping west from `x == 0` on unsigned points).
+    pub fn step(&self, dir: Direction) -> Option<Self> {
+        let offset = dir.offset();
+        let x: i64 = self.x.try_into().ok()? + offset.x as i64;
+        let y: i64 = self.y.try_into().ok()? + offset.y as i64;
+        Some(Self::new(T::try_from(x).ok()?, T::try_from(y).ok()?))
+    }
+
+    /// Adjacent points in the given directions, skipping the ones that do not
+    /// fit in `T`.
+    pub fn neighbours<'a>(&self, dirs: &'a [Direction]) -> impl Iterator<Item = Self> + 'a
+    where
+        T: 'a,
+    {
+        let origin = *self;
+        dirs.iter().filter_map(move |&dir| origin.step(dir))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from(value: (T, T)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Compass directions, north pointing up the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The four directions along the axes, clockwise from north.
+    pub const CARDINAL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+    /// The four diagonal directions, clockwise from north-east.
+    pub const DIAGONAL: [Self; 4] = [
+        Self::NorthEast,
+        Self::SouthEast,
+        Self::SouthWest,
+        Self::NorthWest,
+    ];
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// Unit offset of a single step in this direction.
+    pub const fn offset(self) -> Point<i32> {
+        match self {
+            Self::North => Point::new(0, -1),
+            Self::NorthEast => Point::new(1, -1),
+            Self::East => Point::new(1, 0),
+            Self::SouthEast => Point::new(1, 1),
+            Self::South => Point::new(0, 1),
+            Self::SouthWest => Point::new(-1, 1),
+            Self::West => Point::new(-1, 0),
+            Self::NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::NorthEast => Self::SouthWest,
+            Self::East => Self::West,
+            Self::SouthEast => Self::NorthWest,
+            Self::South => Self::North,
+            Self::SouthWest => Self::NorthEast,
+            Self::West => Self::East,
+            Self::NorthWest => Self::SouthEast,
+        }
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Self::NorthEast | Self::SouthEast | Self::SouthWest | Self::NorthWest
+        )
+    }
+}
+
+/// Half-open rectangle of points, `start` included and `end` excluded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Area<T = i32> {
+    pub start: Point<T>,
+    pub end: Point<T>,
+}
+
+impl<T> Area<T> {
+    pub const fn new(start: Point<T>, end: Point<T>) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<T: Copy + PartialOrd> Area<T> {
+    pub fn contains(&self, pos: Point<T>) -> bool {
+        self.start.x <= pos.x && pos.x < self.end.x && self.start.y <= pos.y && pos.y < self.end.y
+    }
+}
+
+impl<T> Area<T>
+where
+    T: Copy + PartialOrd + TryInto<i64> + TryFrom<i64>,
+{
+    /// Points touching the area from the outside, diagonals included.
+    ///
+    /// Points that do not fit in `T` are skipped, points past `end` are not,
+    /// so callers usually filter the result with their own bounds.
+    pub fn surrounding(&self) -> Vec<Point<T>> {
+        let (Ok(x0), Ok(y0), Ok(x1), Ok(y1)) = (
+            self.start.x.try_into(),
+            self.start.y.try_into(),
+            self.end.x.try_into(),
+            self.end.y.try_into(),
+        ) else {
+            return Vec::new();
+        };
+        let (x0, y0, x1, y1): (i64, i64, i64, i64) = (x0, y0, x1, y1);
+        let mut ring = Vec::new();
+        let mut push = |x: i64, y: i64| {
+            if let (Ok(x), Ok(y)) = (T::try_from(x), T::try_from(y)) {
+                ring.push(Point::new(x, y));
+            }
+        };
+        for x in x0 - 1..=x1 {
+            push(x, y0 - 1);
+            push(x, y1);
+        }
+        for y in y0..y1 {
+            push(x0 - 1, y);
+            push(x1, y);
+        }
+        ring
+    }
+}
+
+/// A rectangular grid of cells stored row by row.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Wraps `cells` laid out row-major, panics when the size does not match.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "grid size mismatch");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Builds a grid from text, one row per line and one cell per char.
+    ///
+    /// `cell` returns `None` for chars it does not know, which is reported as
+    /// `expected <expected>` at that position, as are lines of uneven width.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(Point<usize>, char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = 0;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            let mut row = 0;
+            for (x, c) in line.chars().enumerate() {
+                let value = cell(Point::new(x, y), c).ok_or_else(|| {
+                    ParseError::new(
+                        y + 1,
+                        x + 1,
+                        &c.to_string(),
+                        format!("expected {}", expected),
+                    )
+                })?;
+                cells.push(value);
+                row += 1;
+            }
+            if y == 0 {
+                width = row;
+            }
+            if width != row {
+                let message = format!("expected {} cells, found {}", width, row);
+                return Err(ParseError::new(y + 1, row.min(width) + 1, "", message));
+            }
+            height += 1;
+        }
+        Ok(Self::new(width, height, cells))
+    }
+
+    /// Appends a row, padding it with `fill` up to the width of the grid, or
+    /// widening every earlier row when it is longer.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>, fill: T)
+    where
+        T: Clone,
+    {
+        let mut row: Vec<T> = row.into_iter().collect();
+        if row.len() > self.width {
+            let mut old = std::mem::take(&mut self.cells).into_iter();
+            self.cells = Vec::with_capacity(row.len() * (self.height + 1));
+            for _ in 0..self.height {
+                self.cells.extend(old.by_ref().take(self.width));
+                self.cells
+                    .resize(self.cells.len() + row.len() - self.width, fill.clone());
+            }
+            self.width = row.len();
+        }
+        row.resize(self.width, fill);
+        self.cells.append(&mut row);
+        self.height += 1;
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn area(&self) -> Area<usize> {
+        Area::new(Point::new(0, 0), Point::new(self.width, self.height))
+    }
+
+    /// Row-major index of `pos`, `None` when outside of the grid.
+    pub fn index_of<C>(&self, pos: Point<C>) -> Option<usize>
+    where
+        C: TryInto<usize>,
+    {
+        let x: usize = pos.x.try_into().ok()?;
+        let y: usize = pos.y.try_into().ok()?;
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Position of the cell at row-major `index`.
+    pub fn position_of(&self, index: usize) -> Point<usize> {
+        Point::new(index % self.width, index / self.width)
+    }
+
+    pub fn contains<C: TryInto<usize>>(&self, pos: Point<C>) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    pub fn get<C: TryInto<usize>>(&self, pos: Point<C>) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<C: TryInto<usize>>(&mut self, pos: Point<C>) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (Point::new(i % width, i / width), cell))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point<usize>, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| (Point::new(i % width, i / width), cell))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Cells next to `pos` in the given directions that are inside the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Point<usize>,
+        dirs: &'a [Direction],
+    ) -> impl Iterator<Item = (Point<usize>, &'a T)> + 'a {
+        pos.neighbours(dirs)
+            .filter_map(move |ngb| self.get(ngb).map(|cell| (ngb, cell)))
+    }
+
+    /// Applies `f` to every cell keeping the layout.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+    fn index(&self, pos: Point<usize>) -> &Self::Output {
+        self.get(pos).expect("position outside of the grid")
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point<usize>) -> &mut Self::Output {
+        self.get_mut(pos).expect("position outside of the grid")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y != 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 6);
+        let b = Point::new(5, 11);
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 5);
+        assert_eq!(Point::<u32>::new(5, 1).manhattan(Point::new(1, 5)), 8);
+    }
+
+    #[test]
+    fn unsigned_steps_stop_at_zero() {
+        let origin = Point::<usize>::new(0, 0);
+        assert_eq!(origin.step(Direction::West), None);
+        assert_eq!(origin.step(Direction::SouthEast), Some(Point::new(1, 1)));
+        assert_eq!(origin.neighbours(&Direction::ALL).count(), 3);
+        assert_eq!(Point::new(0, 0).neighbours(&Direction::ALL).count(), 8);
+    }
+
+    #[test]
+    fn area_surrounding() {
+        let area = Area::<u32>::new(Point::new(0, 0), Point::new(3, 1));
+        let mut ring = area.surrounding();
+        ring.sort();
+        let expected: Vec<Point<u32>> = vec![(0, 1), (1, 1), (2, 1), (3, 0), (3, 1)]
+            .into_iter()
+            .map(Point::from)
+            .collect();
+        assert_eq!(ring, expected);
+        assert_eq!(
+            Area::new(Point::new(2, 2), Point::new(4, 3))
+                .surrounding()
+                .len(),
+            10
+        );
+    }
+
+    #[test]
+    fn grid_is_bounds_checked() {
+        let grid = Grid::parse("ab\ncd", "a letter", |_, c| Some(c)).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.get(Point::new(1, 1)), Some(&'d'));
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        let ngbs: Vec<char> = grid
+            .neighbours(Point::new(0, 0), &Direction::ALL)
+            .map(|(_, &c)| c)
+            .collect();
+        assert_eq!(ngbs, ['b', 'd', 'c']);
+        assert_eq!(grid.to_string(), "ab\ncd");
+    }
+
+    #[test]
+    fn grid_rejects_ragged_lines() {
+        let err = Grid::parse("ab\nc", "a letter", |_, c| Some(c)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = Grid::parse("ab\ncx", "a letter", |_, c| (c != 'x').then_some(c)).unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 2, "x"));
+    }
+
+    #[test]
+    fn grid_push_ragged_rows() {
+        let mut grid = Grid::default();
+        grid.push_row(['a'], '.');
+        grid.push_row([], '.');
+        grid.push_row(['b', 'c', 'd'], '.');
+        grid.push_row(['e', 'f'], '.');
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid.to_string(), "a..\n...\nbcd\nef.");
+    }
+}
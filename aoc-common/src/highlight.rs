@@ -0,0 +1,28 @@
+//! Marking parts of rendered text, in colour on a terminal.
+
+use std::io::{stdout, IsTerminal};
+
+/// How renderings mark what they highlight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// ANSI colours, for terminals.
+    Ansi,
+    /// Bracket markers, readable without colours.
+    Plain,
+}
+
+impl Highlight {
+    /// `Ansi` when stdout is a terminal, `Plain` when it is redirected.
+    pub fn for_stdout() -> Self {
+        if stdout().is_terminal() {
+            Highlight::Ansi
+        } else {
+            Highlight::Plain
+        }
+    }
+}
+
+/// `text` in the ANSI style `code`, such as `1;32` for bold green.
+pub fn paint(code: &str, text: &str) -> String {
+    format!("\x1b[{}m{}\x1b[0m", code, text)
+}
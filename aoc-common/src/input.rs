@@ -0,0 +1,113 @@
+//! Reading puzzle input from files, stdin or any reader.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead},
+    path::Path,
+};
+
+use crate::ParseError;
+
+/// Reads the input at `path`, or the whole of stdin when `path` is `-`.
+pub fn read(path: &Path) -> io::Result<String> {
+    if is_stdin(path) {
+        read_all(io::stdin().lock())
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
+
+/// Whether `path` names stdin rather than a file.
+pub fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// Reads everything left in `reader`.
+pub fn read_all(mut reader: impl BufRead) -> io::Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Input that could not be read or could not be parsed.
+#[derive(Debug)]
+pub enum InputError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Io(err) => write!(f, "failed to read input: {}", err),
+            InputError::Parse(err) => write!(f, "line {}: {}", err.line, err),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io(err) => Some(err),
+            InputError::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for InputError {
+    fn from(err: io::Error) -> Self {
+        InputError::Io(err)
+    }
+}
+
+impl From<ParseError> for InputError {
+    fn from(err: ParseError) -> Self {
+        InputError::Parse(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Solution, SolveError};
+
+    struct Sum(u32);
+
+    impl Solution for Sum {
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> Result<Self, ParseError> {
+            let mut sum = 0;
+            for line in crate::parse::lines(input) {
+                sum += line.parse::<u32>(line.text, "a number")?;
+            }
+            Ok(Self(sum))
+        }
+
+        fn part1(&self) -> Result<u32, SolveError> {
+            Ok(self.0)
+        }
+
+        fn part2(&self) -> Result<u32, SolveError> {
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn solution_from_reader() {
+        let puzzle = Sum::from_reader("1\n2\n3\n".as_bytes()).unwrap();
+        assert_eq!(puzzle.part1(), Ok(6));
+        match Sum::from_reader(io::Cursor::new("1\nx\n")) {
+            Err(InputError::Parse(err)) => assert_eq!((err.line, err.token.as_str()), (2, "x")),
+            other => panic!("expected a parse error, got {:?}", other.map(|sum| sum.0)),
+        }
+    }
+
+    #[test]
+    fn stdin_path() {
+        assert!(is_stdin(Path::new("-")));
+        assert!(!is_stdin(Path::new("./-")));
+    }
+}
@@ -0,0 +1,16 @@
+//! Building blocks shared by all Advent of Code 2023 days.
+
+mod cli;
+pub mod geometry;
+mod highlight;
+pub mod input;
+pub mod logger;
+pub mod parse;
+pub mod random;
+mod solution;
+
+pub use cli::day_main;
+pub use highlight::{paint, Highlight};
+pub use input::InputError;
+pub use parse::ParseError;
+pub use solution::{Solution, SolveError};
@@ -0,0 +1,201 @@
+//! Input parsing helpers reporting where malformed input went wrong.
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// Malformed puzzle input, pointing at the offending token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the input.
+    pub line: usize,
+    /// 1-based column (in chars) where `token` starts.
+    pub column: usize,
+    /// The offending text, empty when something is missing.
+    pub token: String,
+    /// What was expected instead.
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, token: &str, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            token: String::from(token),
+            message: message.into(),
+        }
+    }
+
+    /// Renders the error with the input line and a caret under the token.
+    pub fn diagnostic(&self, input: &str) -> String {
+        let mut out = format!(
+            "error: {}\n --> line {}, column {}\n",
+            self, self.line, self.column
+        );
+        if let Some(text) = input.lines().nth(self.line.saturating_sub(1)) {
+            let number = self.line.to_string();
+            let pad = " ".repeat(number.len());
+            let indent = " ".repeat(self.column.saturating_sub(1));
+            let carets = "^".repeat(self.token.chars().count().max(1));
+            out += &format!(
+                "{} |\n{} | {}\n{} | {}{}\n",
+                pad, number, text, pad, indent, carets
+            );
+        }
+        out
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.token.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}, found `{}`", self.message, self.token)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// One line of the input, knowing its number so that errors can point at it.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    /// 1-based line number.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Self {
+        Self { number, text }
+    }
+
+    /// 1-based column of `token`, which must be a slice of this line. Tokens
+    /// from elsewhere point past the end of the line.
+    pub fn column(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+        if offset <= self.text.len() && self.text.is_char_boundary(offset) {
+            self.text[..offset].chars().count() + 1
+        } else {
+            self.text.chars().count() + 1
+        }
+    }
+
+    pub fn error(&self, token: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column(token), token, message)
+    }
+
+    /// Error for something missing at the end of `after`, a slice of this line.
+    pub fn missing(&self, after: &'a str, message: impl Into<String>) -> ParseError {
+        self.error(&after[after.len()..], message)
+    }
+
+    /// Parses `token` (a slice of this line), describing it as `what` on error.
+    pub fn parse<T: FromStr>(&self, token: &str, what: &str) -> Result<T, ParseError> {
+        token
+            .parse()
+            .map_err(|_| self.error(token, format!("expected {}", what)))
+    }
+
+    /// Splits `within` (a slice of this line) at the first `separator`.
+    pub fn split_once(
+        &self,
+        within: &'a str,
+        separator: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        within
+            .split_once(separator)
+            .ok_or_else(|| self.missing(within, format!("expected `{}`", separator)))
+    }
+
+    /// Parses every whitespace separated token of `within` as `what`.
+    pub fn numbers<T: FromStr>(&self, within: &'a str, what: &str) -> Result<Vec<T>, ParseError> {
+        within
+            .split_whitespace()
+            .map(|token| self.parse(token, what))
+            .collect()
+    }
+}
+
+/// Numbered lines of `input`.
+pub fn lines(input: &str) -> Lines<'_> {
+    Lines {
+        inner: input.lines(),
+        number: 0,
+    }
+}
+
+/// Iterator over the numbered lines of an input, see [`lines`].
+#[derive(Debug, Clone)]
+pub struct Lines<'a> {
+    inner: std::str::Lines<'a>,
+    number: usize,
+}
+
+impl<'a> Lines<'a> {
+    /// Takes the next line, failing with "unexpected end of input" after the
+    /// last one.
+    pub fn expect(&mut self, what: &str) -> Result<Line<'a>, ParseError> {
+        self.next().ok_or_else(|| {
+            ParseError::new(
+                self.number + 1,
+                1,
+                "",
+                format!("unexpected end of input, expected {}", what),
+            )
+        })
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.inner.next()?;
+        self.number += 1;
+        Some(Line::new(self.number, text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_columns() {
+        let mut lines = lines("first\nGame 12: 3 blüe, x red");
+        lines.next();
+        let line = lines.next().unwrap();
+        let (_, draws) = line.split_once(line.text, ": ").unwrap();
+        let (count, _) = draws.split_once(' ').unwrap();
+        assert_eq!(line.parse::<u32>(count, "a count"), Ok(3));
+        let err = line.parse::<u32>(&draws[9..10], "a count").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 18, "x"));
+        assert_eq!(err.to_string(), "expected a count, found `x`");
+    }
+
+    #[test]
+    fn missing_separator() {
+        let line = Line::new(1, "Card 1");
+        let err = line.split_once(line.text, ": ").unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (7, ""));
+        let mut lines = lines("");
+        assert_eq!(lines.expect("the seeds").unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn caret_diagnostic() {
+        let input = "32T3K 765\nT55X5 684";
+        let err = ParseError::new(2, 4, "X", "expected a card");
+        assert_eq!(
+            err.diagnostic(input),
+            "error: expected a card, found `X`
+ --> line 2, column 4
+  |
+2 | T55X5 684
+  |    ^
+"
+        );
+    }
+}
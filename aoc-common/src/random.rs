@@ -0,0 +1,47 @@
+//! Deterministic pseudo-random numbers for generating benchmark inputs.
+
+/// Marsaglia's xorshift64 generator, always starting from the same state so
+/// every benchmark run sees the same input.
+#[derive(Debug, Clone)]
+pub struct XorShift(u64);
+
+impl XorShift {
+    pub fn new() -> Self {
+        Self(0x2545_f491_4f6c_dd1d)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number below `bound`, which must not be zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+impl Default for XorShift {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_from_the_start() {
+        let first: Vec<u64> = (0..5)
+            .scan(XorShift::new(), |rng, _| Some(rng.below(10)))
+            .collect();
+        let again: Vec<u64> = (0..5)
+            .scan(XorShift::new(), |rng, _| Some(rng.below(10)))
+            .collect();
+        assert_eq!(first, again);
+        assert!(first.iter().all(|&n| n < 10));
+    }
+}
@@ -0,0 +1,53 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    io::BufRead,
+};
+
+use crate::{input, InputError, ParseError};
+
+/// A solved puzzle day.
+///
+/// The input is parsed once into `Self`, both parts are then answered from
+/// the parsed state without touching the input again.
+pub trait Solution: Sized {
+    type Answer1: Display + Debug + PartialEq;
+    type Answer2: Display + Debug + PartialEq;
+
+    /// Builds the puzzle state from the full puzzle input.
+    fn parse(input: &str) -> Result<Self, ParseError>;
+
+    /// Reads the whole of `reader` and parses it, for inputs that are not
+    /// already in memory.
+    fn from_reader(reader: impl BufRead) -> Result<Self, InputError> {
+        let input = input::read_all(reader)?;
+        Ok(Self::parse(&input)?)
+    }
+
+    fn part1(&self) -> Result<Self::Answer1, SolveError>;
+
+    fn part2(&self) -> Result<Self::Answer2, SolveError>;
+}
+
+/// A well formed input that has no answer, e.g. a path that never arrives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    /// Why there is no answer.
+    pub message: String,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no answer, {}", self.message)
+    }
+}
+
+impl Error for SolveError {}
@@ -0,0 +1,105 @@
+//! Repeated timing of the parse and solve stages of a day.
+
+use std::{
+    fmt::Display,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{ParseError, Solution};
+
+/// How many times each stage runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// Untimed runs before sampling starts.
+    pub warmup: usize,
+    /// Timed runs, at least one.
+    pub iterations: usize,
+}
+
+/// Summary of the samples of one stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarises `samples`, which must not be empty.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort_unstable();
+        // nearest rank percentile
+        let rank = |percent: usize| (samples.len() * percent).div_ceil(100).max(1) - 1;
+        Self {
+            min: samples[0],
+            median: samples[rank(50)],
+            p95: samples[rank(95)],
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>10} {:>10} {:>10}",
+            format!("{:.1?}", self.min),
+            format!("{:.1?}", self.median),
+            format!("{:.1?}", self.p95)
+        )
+    }
+}
+
+/// Timings of every stage of one day.
+#[derive(Debug, Clone, Copy)]
+pub struct Report {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+fn sample<T>(options: &Options, mut stage: impl FnMut() -> T) -> Stats {
+    for _ in 0..options.warmup {
+        black_box(stage());
+    }
+    let samples = (0..options.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(stage());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+/// Times parsing `input` and answering both parts from the parsed state.
+pub fn measure<S: Solution>(input: &str, options: &Options) -> Result<Report, ParseError> {
+    let puzzle = S::parse(input)?;
+    Ok(Report {
+        parse: sample(options, || S::parse(black_box(input))),
+        part1: sample(options, || puzzle.part1()),
+        part2: sample(options, || puzzle.part2()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles() {
+        let samples = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+
+        let single = Stats::from_samples(vec![Duration::from_micros(3)]);
+        assert_eq!(
+            (single.min, single.median, single.p95),
+            (single.min, single.min, single.min)
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Subcommands exploring a single day beyond its two answers.
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+
+use aoc_common::{input, ParseError};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+
+/// Where a day reads its puzzle input from, shared by `run`, `bench` and the
+/// day subcommands.
+#[derive(Debug, Args)]
+pub struct InputArgs {
+    /// Directory holding `dayNN.txt` inputs, used when no input file is given
+    #[arg(long, default_value = "inputs")]
+    inputs: PathBuf,
+    /// Puzzle input, `-` for stdin, defaults to `<inputs>/dayNN.txt`
+    input: Option<PathBuf>,
+}
+
+impl InputArgs {
+    /// Directory holding the `dayNN.txt` inputs.
+    pub fn inputs(&self) -> &Path {
+        &self.inputs
+    }
+
+    /// `<inputs>/dayNN.txt`, whatever input file was given.
+    pub fn default_path(&self, day: u8) -> PathBuf {
+        self.inputs.join(format!("day{:02}.txt", day))
+    }
+
+    /// The input file given, or else [`InputArgs::default_path`].
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.input.clone().unwrap_or_else(|| self.default_path(day))
+    }
+
+    /// Reads the input of `day`, reporting failures on stderr.
+    pub fn read(&self, day: u8) -> Option<String> {
+        read(&self.path(day))
+    }
+}
+
+/// Reads `path` (`-` for stdin), reporting failures on stderr.
+pub fn read(path: &Path) -> Option<String> {
+    match input::read(path) {
+        Ok(text) => Some(text),
+        Err(err) => {
+            eprintln!("Failed to read {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+/// Reports malformed `text` read from `what` on stderr.
+pub fn report(what: &str, text: &str, err: &ParseError) {
+    eprintln!("Malformed {}", what);
+    eprint!("{}", err.diagnostic(text));
+}
@@ -0,0 +1,54 @@
+use std::{path::Path, process::ExitCode};
+
+use clap::Args;
+
+use day01::{Highlight, Vocabulary};
+
+use super::InputArgs;
+
+#[derive(Debug, Args)]
+pub struct Day01Args {
+    /// Number words to recognise: a preset (digits, english, extended,
+    /// german, czech) or a file of `word = number` lines
+    #[arg(long, default_value = "english")]
+    vocabulary: String,
+    /// Show every line with the tokens behind its calibration value marked
+    #[arg(short, long)]
+    explain: bool,
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+fn vocabulary(name: &str) -> Option<Vocabulary> {
+    if let Some(vocabulary) = Vocabulary::preset(name) {
+        return Some(vocabulary);
+    }
+    let text = super::read(Path::new(name))?;
+    match Vocabulary::parse(&text) {
+        Ok(vocabulary) => Some(vocabulary),
+        Err(err) => {
+            super::report(&format!("vocabulary {}", name), &text, &err);
+            None
+        }
+    }
+}
+
+/// Sums the calibration values of the input decoded with the chosen
+/// vocabulary, explaining each line on request.
+pub fn run(args: Day01Args) -> ExitCode {
+    let Some(vocabulary) = vocabulary(&args.vocabulary) else {
+        return ExitCode::FAILURE;
+    };
+    let Some(document) = args.input.read(1) else {
+        return ExitCode::FAILURE;
+    };
+    let decoder = vocabulary.decoder();
+    if args.explain {
+        let highlight = Highlight::for_stdout();
+        for line in document.lines() {
+            println!("{}", decoder.decode(line).explain(line, highlight));
+        }
+    }
+    println!("{}", decoder.total(&document));
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,60 @@
+use std::process::ExitCode;
+
+use clap::Args;
+
+use aoc_common::Solution;
+use day02::{Bag, Constraints, Puzzle};
+
+use super::InputArgs;
+
+#[derive(Debug, Args)]
+pub struct Day02Args {
+    /// Cubes of each colour in the bag, as `red=12,green=13,blue=14`;
+    /// colours not listed are not in the bag
+    #[arg(long, value_parser = parse_bag)]
+    bag: Option<Bag>,
+    /// Cubes in the bag altogether
+    #[arg(long)]
+    total: Option<u64>,
+    /// Cubes the elf shows at most in one draw
+    #[arg(long)]
+    per_draw: Option<u64>,
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+fn parse_bag(text: &str) -> Result<Bag, String> {
+    Bag::parse(text).map_err(|err| format!("column {}: {}", err.column, err))
+}
+
+/// Checks every game against the constraints, reporting why failing games
+/// are impossible, and sums the ids of the possible ones.
+pub fn run(args: Day02Args) -> ExitCode {
+    let Some(text) = args.input.read(2) else {
+        return ExitCode::FAILURE;
+    };
+    let puzzle = match Puzzle::parse(&text) {
+        Ok(puzzle) => puzzle,
+        Err(err) => {
+            super::report("day 2 input", &text, &err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let constraints = Constraints {
+        bag: args.bag,
+        total: args.total,
+        per_draw: args.per_draw,
+    };
+    let mut sum: u64 = 0;
+    for game in puzzle.games() {
+        match constraints.violation(game) {
+            Some(violation) => println!("Game {}: impossible, {}", game.id, violation),
+            None => {
+                println!("Game {}: possible", game.id);
+                sum += u64::from(game.id);
+            }
+        }
+    }
+    println!("Sum of possible game ids: {}", sum);
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,48 @@
+use std::process::ExitCode;
+
+use clap::Args;
+
+use aoc_common::{Highlight, Solution};
+use day03::{Gear, Puzzle};
+
+use super::InputArgs;
+
+#[derive(Debug, Args)]
+pub struct Day03Args {
+    /// Symbol counting as a gear
+    #[arg(long, default_value_t = '*')]
+    gear: char,
+    /// How many numbers a gear touches
+    #[arg(long, default_value_t = 2)]
+    arity: usize,
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+/// Redraws the schematic with part numbers and gears highlighted, followed
+/// by both sums.
+pub fn run(args: Day03Args) -> ExitCode {
+    let Ok(symbol) = u8::try_from(args.gear) else {
+        eprintln!("Gear symbol `{}` is not ASCII", args.gear);
+        return ExitCode::FAILURE;
+    };
+    let Some(text) = args.input.read(3) else {
+        return ExitCode::FAILURE;
+    };
+    let puzzle = match Puzzle::parse(&text) {
+        Ok(puzzle) => puzzle,
+        Err(err) => {
+            super::report("day 3 input", &text, &err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let schematic = puzzle.schematic();
+    let gear = Gear {
+        symbol,
+        arity: args.arity,
+    };
+    print!("{}", schematic.render(gear, Highlight::for_stdout()));
+    println!("Part numbers: {}", schematic.part_numbers_sum());
+    println!("Gear ratios: {}", schematic.gear_ratios(gear));
+    ExitCode::SUCCESS
+}
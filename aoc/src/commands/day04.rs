@@ -0,0 +1,83 @@
+use std::process::ExitCode;
+
+use clap::Args;
+
+use aoc_common::Solution;
+use day04::Puzzle;
+
+use super::InputArgs;
+
+#[derive(Debug, Args)]
+pub struct Day04Args {
+    /// Print every card with its matches, copies and the cards that won them
+    #[arg(long, short)]
+    trace: bool,
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+/// Plays the pile of scratchcards and reports how the copies cascaded.
+pub fn run(args: Day04Args) -> ExitCode {
+    let Some(text) = args.input.read(4) else {
+        return ExitCode::FAILURE;
+    };
+    let puzzle = match Puzzle::parse(&text) {
+        Ok(puzzle) => puzzle,
+        Err(err) => {
+            super::report("day 4 input", &text, &err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let cards = puzzle.cards();
+    let Some(pile) = puzzle.pile() else {
+        eprintln!("The copies of a card overflow a u64");
+        return ExitCode::FAILURE;
+    };
+    if args.trace {
+        println!("{:>6} {:>8} {:>10}  won from", "card", "matches", "copies");
+        for (card, step) in cards.iter().zip(pile.cascade()) {
+            let won_from = if step.won_from.is_empty() {
+                "-".to_string()
+            } else {
+                let ids: Vec<_> = step
+                    .won_from
+                    .iter()
+                    .map(|&from| cards[from].id().to_string())
+                    .collect();
+                ids.join(" ")
+            };
+            println!(
+                "{:>6} {:>8} {:>10}  {}",
+                card.id(),
+                step.matches,
+                step.copies,
+                won_from
+            );
+        }
+    }
+    println!("Cards: {}", cards.len());
+    match puzzle.part1() {
+        Ok(points) => println!("Points: {}", points),
+        Err(err) => eprintln!("Points: {}", err),
+    }
+    match pile.total() {
+        Some(total) => println!("Copies: {}", total),
+        None => println!("Copies: more than {}", u64::MAX),
+    }
+    if let Some((card, copies)) = pile.most_copied() {
+        println!("Most copied: card {} ({} copies)", cards[card].id(), copies);
+    }
+    let chain = pile.longest_cascade();
+    if !chain.is_empty() {
+        let ids: Vec<_> = chain
+            .iter()
+            .map(|&card| cards[card].id().to_string())
+            .collect();
+        println!(
+            "Longest cascade: {} ({} cards)",
+            ids.join(" -> "),
+            chain.len()
+        );
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,65 @@
+use std::{ops::Range, process::ExitCode};
+
+use clap::Args;
+
+use aoc_common::Solution;
+use day05::Puzzle;
+
+use super::InputArgs;
+
+#[derive(Debug, Args)]
+pub struct Day05Args {
+    /// Seed to look up the location of, may be repeated
+    #[arg(long)]
+    seed: Vec<u64>,
+    /// Seeds `start..end` to look up the locations of, may be repeated
+    #[arg(long, value_parser = parse_range)]
+    range: Vec<Range<u64>>,
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+fn parse_range(text: &str) -> Result<Range<u64>, String> {
+    let (start, end) = text
+        .split_once("..")
+        .ok_or_else(|| "expected `start..end`".to_string())?;
+    let start = start.trim().parse().map_err(|_| "expected a range start")?;
+    let end = end.trim().parse().map_err(|_| "expected a range end")?;
+    Ok(start..end)
+}
+
+/// Composes the almanac into one seed-to-location map, prints it as a table
+/// and looks up the requested seeds in it.
+pub fn run(args: Day05Args) -> ExitCode {
+    let Some(text) = args.input.read(5) else {
+        return ExitCode::FAILURE;
+    };
+    let puzzle = match Puzzle::parse(&text) {
+        Ok(puzzle) => puzzle,
+        Err(err) => {
+            super::report("day 5 input", &text, &err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let almanac = puzzle.almanac();
+    println!("seed-to-location map:");
+    print!("{}", almanac);
+    for seed in args.seed {
+        println!("Seed {}: location {}", seed, almanac.encode_value(seed));
+    }
+    for range in args.range {
+        let mut locations = almanac.encode_ranges(Some(range.clone()));
+        locations.sort_by_key(|location| location.start);
+        let locations: Vec<_> = locations
+            .iter()
+            .map(|location| format!("{}..{}", location.start, location.end))
+            .collect();
+        println!(
+            "Seeds {}..{}: locations {}",
+            range.start,
+            range.end,
+            locations.join(" ")
+        );
+    }
+    ExitCode::SUCCESS
+}
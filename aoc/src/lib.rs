@@ -0,0 +1,4 @@
+//! Shared pieces of the `aoc` runner, also used by its integration tests.
+
+pub mod bench;
+pub mod registry;
@@ -0,0 +1,266 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+use aoc::{
+    bench,
+    registry::{self, Day},
+};
+use aoc_common::{input, logger, ParseError};
+
+mod commands;
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code 2023 solutions runner")]
+struct Cli {
+    /// Show debug traces of the solutions, twice for every detail
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Only report errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run one day or every day and print the answers with timings
+    Run(RunArgs),
+    /// Time parsing and both parts of one day or every day over many runs
+    Bench(BenchArgs),
+    /// Decode day 1 calibration values with any number vocabulary
+    Day01(commands::day01::Day01Args),
+    /// Check day 2 games against bag, total and per draw cube limits
+    Day02(commands::day02::Day02Args),
+    /// Draw the day 3 schematic with part numbers and gears highlighted
+    Day03(commands::day03::Day03Args),
+    /// Trace how day 4 scratchcard copies cascade down the pile
+    Day04(commands::day04::Day04Args),
+    /// Compose the day 5 almanac into one seed-to-location map
+    Day05(commands::day05::Day05Args),
+}
+
+#[derive(Debug, Args)]
+struct DaySelection {
+    /// Day to run
+    #[arg(short, long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+    /// Run every registered day
+    #[arg(short, long, conflicts_with = "input")]
+    all: bool,
+    #[command(flatten)]
+    input: commands::InputArgs,
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: DaySelection,
+    /// Part to run, both parts when omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// How answers are printed
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// One human readable line per answer
+    Text,
+    /// One JSON record per answer and line, nothing else on stdout
+    Json,
+}
+
+/// A `--format json` line.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: u8,
+    answer: &'a str,
+    parse_ns: u128,
+    solve_ns: u128,
+}
+
+#[derive(Debug, Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    selection: DaySelection,
+    /// Timed runs of every stage
+    #[arg(short, long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+    iterations: u64,
+    /// Untimed runs of every stage before timing starts
+    #[arg(short, long, default_value_t = 10)]
+    warmup: u64,
+}
+
+/// Days picked on the command line with their inputs, `None` when a single
+/// requested day cannot be run. With `--all` days without input are skipped,
+/// and `None` returned when that leaves no day at all.
+fn select(selection: DaySelection) -> Option<Vec<(&'static Day, PathBuf)>> {
+    if selection.all {
+        let mut days = Vec::new();
+        for day in registry::DAYS {
+            let input = selection.input.default_path(day.number);
+            if !input.exists() {
+                log::warn!(
+                    "Day {:02}: skipped, {} not found",
+                    day.number,
+                    input.display()
+                );
+                continue;
+            }
+            days.push((day, input));
+        }
+        if days.is_empty() {
+            eprintln!(
+                "No day has an input in {}",
+                selection.input.inputs().display()
+            );
+            return None;
+        }
+        return Some(days);
+    }
+
+    let number = selection.day.expect("day is required without --all");
+    let Some(day) = registry::find(number) else {
+        eprintln!("Day {} is not solved yet", number);
+        return None;
+    };
+    let input = selection.input.path(number);
+    if !input::is_stdin(&input) && !input.exists() {
+        eprintln!("Input {} not found", input.display());
+        return None;
+    }
+    Some(vec![(day, input)])
+}
+
+fn report_malformed(day: &Day, path: &Path, input: &str, err: &ParseError) {
+    eprintln!("Day {:02}: malformed input {}", day.number, path.display());
+    eprint!("{}", err.diagnostic(input));
+}
+
+/// Runs the requested parts of `day`, returns `false` when the input cannot be
+/// read or is malformed, or a part has no answer.
+fn run_day(day: &Day, parts: &[u8], path: &Path, format: Format) -> bool {
+    let Some(input) = commands::read(path) else {
+        return false;
+    };
+    let timed = match day.timed(&input, parts) {
+        Ok(timed) => timed,
+        Err(err) => {
+            report_malformed(day, path, &input, &err);
+            return false;
+        }
+    };
+    let mut success = true;
+    for answer in &timed.answers {
+        let text = match &answer.answer {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("Day {:02} part {}: {}", day.number, answer.part, err);
+                success = false;
+                continue;
+            }
+        };
+        match format {
+            Format::Text => println!(
+                "Day {:02} part {}: {} (parse {:?}, solve {:?})",
+                day.number, answer.part, text, timed.parse, answer.solve
+            ),
+            Format::Json => {
+                let record = Record {
+                    day: day.number,
+                    part: answer.part,
+                    answer: text,
+                    parse_ns: timed.parse.as_nanos(),
+                    solve_ns: answer.solve.as_nanos(),
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("records always serialize")
+                );
+            }
+        }
+    }
+    success
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let parts: Vec<u8> = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let Some(days) = select(args.selection) else {
+        return ExitCode::FAILURE;
+    };
+    let mut success = true;
+    for (day, input) in days {
+        success &= run_day(day, &parts, &input, args.format);
+    }
+    exit_code(success)
+}
+
+fn bench(args: BenchArgs) -> ExitCode {
+    let options = bench::Options {
+        warmup: args.warmup as usize,
+        iterations: args.iterations as usize,
+    };
+    let Some(days) = select(args.selection) else {
+        return ExitCode::FAILURE;
+    };
+    println!(
+        "{:<5} {:<6} {:>10} {:>10} {:>10}",
+        "day", "stage", "min", "median", "p95"
+    );
+    let mut success = true;
+    for (day, path) in days {
+        let Some(input) = commands::read(&path) else {
+            success = false;
+            continue;
+        };
+        let report = match day.bench(&input, &options) {
+            Ok(report) => report,
+            Err(err) => {
+                report_malformed(day, &path, &input, &err);
+                success = false;
+                continue;
+            }
+        };
+        for (stage, stats) in [
+            ("parse", report.parse),
+            ("part1", report.part1),
+            ("part2", report.part2),
+        ] {
+            println!("{:<5} {:<6} {}", format!("{:02}", day.number), stage, stats);
+        }
+    }
+    exit_code(success)
+}
+
+fn exit_code(success: bool) -> ExitCode {
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    logger::init(logger::level(cli.verbose, cli.quiet));
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::Bench(args) => bench(args),
+        Command::Day01(args) => commands::day01::run(args),
+        Command::Day02(args) => commands::day02::run(args),
+        Command::Day03(args) => commands::day03::run(args),
+        Command::Day04(args) => commands::day04::run(args),
+        Command::Day05(args) => commands::day05::run(args),
+    }
+}
@@ -0,0 +1,133 @@
+use std::time::{Duration, Instant};
+
+use aoc_common::{ParseError, Solution, SolveError};
+
+use crate::bench::{self, Report};
+
+/// The answer of a part as text, or why the input has none.
+pub type Rendered = Result<String, SolveError>;
+
+/// One solved puzzle day, type-erased so that all days fit in one table.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    solve: fn(&str, u8) -> Result<Rendered, ParseError>,
+    timed: fn(&str, &[u8]) -> Result<Timed, ParseError>,
+    bench: fn(&str, &bench::Options) -> Result<Report, ParseError>,
+}
+
+impl Day {
+    const fn new<S: Solution>(number: u8) -> Self {
+        Self {
+            number,
+            solve: solve::<S>,
+            timed: timed::<S>,
+            bench: bench::measure::<S>,
+        }
+    }
+
+    /// Parses `input` and returns the rendered answer of `part`, or why the
+    /// well formed input has none.
+    pub fn solve(&self, input: &str, part: u8) -> Result<Rendered, ParseError> {
+        (self.solve)(input, part)
+    }
+
+    /// Parses `input` once and answers each of `parts`, timing every stage.
+    pub fn timed(&self, input: &str, parts: &[u8]) -> Result<Timed, ParseError> {
+        (self.timed)(input, parts)
+    }
+
+    /// Times parsing `input` and both parts, see [`bench::measure`].
+    pub fn bench(&self, input: &str, options: &bench::Options) -> Result<Report, ParseError> {
+        (self.bench)(input, options)
+    }
+}
+
+/// The answer of one part with the time it took from the parsed puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: u8,
+    pub answer: Rendered,
+    pub solve: Duration,
+}
+
+/// Answers of a single run of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timed {
+    pub parse: Duration,
+    pub answers: Vec<Answer>,
+}
+
+fn answer<S: Solution>(puzzle: &S, part: u8) -> Rendered {
+    match part {
+        1 => puzzle.part1().map(|answer| answer.to_string()),
+        2 => puzzle.part2().map(|answer| answer.to_string()),
+        _ => panic!("unknown part {}", part),
+    }
+}
+
+fn solve<S: Solution>(input: &str, part: u8) -> Result<Rendered, ParseError> {
+    Ok(answer(&S::parse(input)?, part))
+}
+
+fn timed<S: Solution>(input: &str, parts: &[u8]) -> Result<Timed, ParseError> {
+    let start = Instant::now();
+    let puzzle = S::parse(input)?;
+    let parse = start.elapsed();
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = answer(&puzzle, part);
+            Answer {
+                part,
+                answer,
+                solve: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok(Timed { parse, answers })
+}
+
+/// Every day known to the runner, in puzzle order.
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Puzzle>(1),
+    Day::new::<day02::Puzzle>(2),
+    Day::new::<day03::Puzzle>(3),
+    Day::new::<day04::Puzzle>(4),
+    Day::new::<day05::Puzzle>(5),
+    Day::new::<day06::Puzzle>(6),
+    Day::new::<day07::Puzzle>(7),
+    Day::new::<day08::Puzzle>(8),
+    Day::new::<day09::Puzzle>(9),
+    Day::new::<day10::Puzzle>(10),
+    Day::new::<day11::Puzzle>(11),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timed_answers_requested_parts() {
+        let day = find(9).unwrap();
+        let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
+        let timed = day.timed(input, &[2]).unwrap();
+        assert_eq!(timed.answers.len(), 1);
+        assert_eq!(
+            (timed.answers[0].part, timed.answers[0].answer.as_deref()),
+            (2, Ok("2"))
+        );
+        assert_eq!(day.solve(input, 1).unwrap().as_deref(), Ok("114"));
+
+        let day = find(8).unwrap();
+        let answer = day.solve("L\n\nBBB = (BBB, BBB)", 1).unwrap();
+        assert!(answer.is_err());
+        assert!(day.timed("1 x", &[1, 2]).is_err());
+        assert!(find(25).is_none());
+    }
+}
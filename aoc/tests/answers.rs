@@ -0,0 +1,75 @@
+//! Checks the real puzzle inputs against recorded answers.
+//!
+//! Inputs are personal, so they are not committed and this test is ignored by
+//! default. Put `dayNN.txt` files and an `answers.toml` like
+//!
+//! ```toml
+//! [day07]
+//! part1 = 250232501
+//! part2 = 249138943
+//! ```
+//!
+//! into `inputs/` (or the directory named by `AOC_INPUTS`) and run
+//! `cargo test -p aoc -- --ignored`.
+
+use std::path::PathBuf;
+
+use aoc::registry;
+use toml::{Table, Value};
+
+fn inputs_dir() -> PathBuf {
+    match std::env::var_os("AOC_INPUTS") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../inputs"),
+    }
+}
+
+fn expected(value: &Value) -> String {
+    match value {
+        Value::String(answer) => answer.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[test]
+#[ignore = "needs personal inputs and inputs/answers.toml"]
+fn recorded_answers() {
+    let dir = inputs_dir();
+    let path = dir.join("answers.toml");
+    let answers: Table = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err))
+        .parse()
+        .unwrap_or_else(|err| panic!("malformed {}: {}", path.display(), err));
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for day in registry::DAYS {
+        let name = format!("day{:02}", day.number);
+        let Some(parts) = answers.get(&name).and_then(Value::as_table) else {
+            continue;
+        };
+        let input_path = dir.join(format!("{}.txt", name));
+        let input = std::fs::read_to_string(&input_path)
+            .unwrap_or_else(|err| panic!("failed to read {}: {}", input_path.display(), err));
+        for part in 1..=2 {
+            let Some(value) = parts.get(&format!("part{}", part)) else {
+                continue;
+            };
+            let expected = expected(value);
+            let actual = match day.solve(&input, part) {
+                Ok(Ok(answer)) => answer,
+                Ok(Err(err)) => err.to_string(),
+                Err(err) => panic!("{}: {}", name, err.diagnostic(&input)),
+            };
+            checked += 1;
+            if actual != expected {
+                failures.push(format!(
+                    "{} part {}: expected {}, got {}",
+                    name, part, expected, actual
+                ));
+            }
+        }
+    }
+    assert!(checked > 0, "no answers recorded in {}", path.display());
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
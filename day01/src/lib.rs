@@ -0,0 +1,570 @@
+//! Day 1: Trebuchet?!
+
+use std::cmp::Reverse;
+
+pub use aoc_common::Highlight;
+use aoc_common::{paint, parse, ParseError, Solution, SolveError};
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+const ENGLISH: [&str; 21] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+];
+const GERMAN: [&str; 21] = [
+    "null",
+    "eins",
+    "zwei",
+    "drei",
+    "vier",
+    "fünf",
+    "sechs",
+    "sieben",
+    "acht",
+    "neun",
+    "zehn",
+    "elf",
+    "zwölf",
+    "dreizehn",
+    "vierzehn",
+    "fünfzehn",
+    "sechzehn",
+    "siebzehn",
+    "achtzehn",
+    "neunzehn",
+    "zwanzig",
+];
+const CZECH: [&str; 21] = [
+    "nula",
+    "jedna",
+    "dva",
+    "tři",
+    "čtyři",
+    "pět",
+    "šest",
+    "sedm",
+    "osm",
+    "devět",
+    "deset",
+    "jedenáct",
+    "dvanáct",
+    "třináct",
+    "čtrnáct",
+    "patnáct",
+    "šestnáct",
+    "sedmnáct",
+    "osmnáct",
+    "devatenáct",
+    "dvacet",
+];
+
+/// The words a [`CalibrationDecoder`] recognises, each with its number.
+///
+/// Built either in code,
+///
+/// ```
+/// # use day01::Vocabulary;
+/// let vocabulary = Vocabulary::digits().word("zero", 0).word("ten", 10);
+/// ```
+///
+/// or parsed from `word = number` lines, see [`Vocabulary::parse`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// Names accepted by [`Vocabulary::preset`].
+    pub const PRESETS: [&'static str; 5] = ["digits", "english", "extended", "german", "czech"];
+
+    /// An empty vocabulary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `word` standing for `number`. Several words may share a number.
+    ///
+    /// # Panics
+    ///
+    /// When `word` is empty.
+    pub fn word(mut self, word: &str, number: u32) -> Self {
+        assert!(!word.is_empty(), "empty calibration word");
+        self.words.push((String::from(word), number));
+        self
+    }
+
+    /// Adds the words of `numbers` for 0, 1, 2 and so on.
+    pub fn numbers(self, numbers: &[&str]) -> Self {
+        numbers
+            .iter()
+            .zip(0..)
+            .fold(self, |vocabulary, (word, number)| {
+                vocabulary.word(word, number)
+            })
+    }
+
+    /// The numeric digits `0` to `9`, part one of the puzzle.
+    pub fn digits() -> Self {
+        Self::new().numbers(&DIGITS)
+    }
+
+    /// Digits and `one` to `nine`, part two of the puzzle.
+    pub fn english() -> Self {
+        let words = ENGLISH[1..10].iter().zip(1..);
+        words.fold(Self::digits(), |vocabulary, (word, number)| {
+            vocabulary.word(word, number)
+        })
+    }
+
+    /// Digits and English words from `zero` to `twenty`.
+    pub fn extended() -> Self {
+        Self::digits().numbers(&ENGLISH)
+    }
+
+    /// Digits and German words from `null` to `zwanzig`.
+    pub fn german() -> Self {
+        Self::digits().numbers(&GERMAN)
+    }
+
+    /// Digits and Czech words from `nula` to `dvacet`, including the `jeden`
+    /// and `dvě` forms.
+    pub fn czech() -> Self {
+        Self::digits()
+            .numbers(&CZECH)
+            .word("jeden", 1)
+            .word("dvě", 2)
+    }
+
+    /// One of the [`Vocabulary::PRESETS`] by name.
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "digits" => Some(Self::digits()),
+            "english" => Some(Self::english()),
+            "extended" => Some(Self::extended()),
+            "german" => Some(Self::german()),
+            "czech" => Some(Self::czech()),
+            _ => None,
+        }
+    }
+
+    /// Parses one `word = number` entry per line. Blank lines and lines
+    /// starting with `#` are skipped.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut vocabulary = Self::new();
+        for line in parse::lines(text) {
+            let entry = line.text.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let (word, number) = line.split_once(entry, "=")?;
+            let word = word.trim();
+            if word.is_empty() {
+                return Err(line.error(word, "expected a word before `=`"));
+            }
+            let number = line.parse(number.trim(), "a number")?;
+            vocabulary = vocabulary.word(word, number);
+        }
+        Ok(vocabulary)
+    }
+
+    /// Every word with its number.
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words
+            .iter()
+            .map(|(word, number)| (word.as_str(), *number))
+    }
+
+    pub fn decoder(&self) -> CalibrationDecoder {
+        CalibrationDecoder::new(self.words())
+    }
+}
+
+/// A number found in a line: its value and the byte range it spans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub value: u32,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// First and last number of a line, both `None` when it holds none.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Calibration {
+    pub first: Option<Token>,
+    pub last: Option<Token>,
+}
+
+impl Calibration {
+    /// The two digit calibration value: the leading digit of the first
+    /// number followed by the trailing digit of the last one.
+    pub fn value(&self) -> Option<u32> {
+        let mut first = self.first?.value;
+        while first >= 10 {
+            first /= 10;
+        }
+        Some(first * 10 + self.last?.value % 10)
+    }
+
+    /// Renders `line`, the decoded line, with its value and the first and
+    /// last tokens marked. Lines without any number are flagged.
+    ///
+    /// In colour the first token is green, the last one blue and a token
+    /// that is both yellow, otherwise they read `[first]` and `{last}`.
+    pub fn explain(&self, line: &str, highlight: Highlight) -> String {
+        let (Some(first), Some(last), Some(value)) = (self.first, self.last, self.value()) else {
+            let flag = match highlight {
+                Highlight::Ansi => paint("1;31", "no number"),
+                Highlight::Plain => String::from("no number"),
+            };
+            return format!("{:>3}  {}  <- {}", "-", line, flag);
+        };
+        let mut bounds = vec![0, first.start, first.end, last.start, last.end, line.len()];
+        bounds.sort_unstable();
+        bounds.dedup();
+        let mut out = format!("{:>3}  ", value);
+        for segment in bounds.windows(2) {
+            let (start, end) = (segment[0], segment[1]);
+            let in_first = first.start <= start && end <= first.end;
+            let in_last = last.start <= start && end <= last.end;
+            match highlight {
+                Highlight::Ansi => {
+                    let segment = &line[start..end];
+                    match (in_first, in_last) {
+                        (true, true) => out += &paint("1;33", segment),
+                        (true, false) => out += &paint("1;32", segment),
+                        (false, true) => out += &paint("1;34", segment),
+                        (false, false) => out += segment,
+                    }
+                }
+                Highlight::Plain => {
+                    if start == first.start {
+                        out.push('[');
+                    }
+                    if start == last.start && last != first {
+                        out.push('{');
+                    }
+                    out += &line[start..end];
+                    if end == last.end && last != first {
+                        out.push('}');
+                    }
+                    if end == first.end {
+                        out.push(']');
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    /// Next state for every byte, failure transitions already folded in.
+    next: [u32; 256],
+    /// Value and length of every token ending in this state, longest first.
+    outputs: Vec<(u32, usize)>,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            next: [0; 256],
+            outputs: Vec::new(),
+        }
+    }
+}
+
+/// Finds number tokens walking each line once with an Aho-Corasick automaton,
+/// so that overlapping tokens like `eightwo` yield both `eight` and `two`.
+#[derive(Debug, Clone)]
+pub struct CalibrationDecoder {
+    states: Vec<State>,
+}
+
+impl CalibrationDecoder {
+    /// Decoder recognising each of `tokens` as the given value.
+    ///
+    /// # Panics
+    ///
+    /// When a token is empty.
+    pub fn new<'a>(tokens: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        // the trie, state 0 being the root
+        let mut states = vec![State::new()];
+        for (token, value) in tokens {
+            assert!(!token.is_empty(), "empty calibration token");
+            let mut state = 0;
+            for &byte in token.as_bytes() {
+                state = match states[state].next[byte as usize] {
+                    0 => {
+                        states.push(State::new());
+                        let child = states.len() - 1;
+                        states[state].next[byte as usize] = child as u32;
+                        child
+                    }
+                    child => child as usize,
+                };
+            }
+            states[state].outputs.push((value, token.len()));
+        }
+
+        // breadth first, so that the failure state of every state is complete
+        // before its children are visited
+        let mut fail = vec![0; states.len()];
+        let mut queue = std::collections::VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..256 {
+                let child = states[state].next[byte] as usize;
+                if child == 0 {
+                    if state != 0 {
+                        states[state].next[byte] = states[fail[state]].next[byte];
+                    }
+                    continue;
+                }
+                if state != 0 {
+                    fail[child] = states[fail[state]].next[byte] as usize;
+                    let inherited = states[fail[child]].outputs.clone();
+                    states[child].outputs.extend(inherited);
+                }
+                queue.push_back(child);
+            }
+        }
+        Self { states }
+    }
+
+    /// Every token of `line`, ordered by where it ends.
+    pub fn matches<'d, 't>(&'d self, line: &'t str) -> Matches<'d, 't> {
+        Matches {
+            decoder: self,
+            text: line.as_bytes(),
+            pos: 0,
+            state: 0,
+            output: 0,
+        }
+    }
+
+    /// First and last token of `line`. Tokens starting at the same offset
+    /// resolve to the longest one.
+    pub fn decode(&self, line: &str) -> Calibration {
+        let mut calibration = Calibration::default();
+        for token in self.matches(line) {
+            let earliest = (token.start, Reverse(token.end));
+            if calibration
+                .first
+                .is_none_or(|first| earliest < (first.start, Reverse(first.end)))
+            {
+                calibration.first = Some(token);
+            }
+            if calibration
+                .last
+                .is_none_or(|last| (token.start, token.end) >= (last.start, last.end))
+            {
+                calibration.last = Some(token);
+            }
+        }
+        calibration
+    }
+
+    /// Decodes every line of `input`.
+    pub fn decode_lines<'a>(&'a self, input: &'a str) -> impl Iterator<Item = Calibration> + 'a {
+        input.lines().map(|line| self.decode(line))
+    }
+
+    /// Sum of the calibration values of `input`, skipping lines without a
+    /// digit.
+    pub fn total(&self, input: &str) -> u32 {
+        input
+            .lines()
+            .zip(self.decode_lines(input))
+            .filter_map(|(line, calibration)| {
+                log::trace!("{} | {:?}", line, calibration);
+                let value = calibration.value();
+                if value.is_none() {
+                    log::debug!("no digit in `{}`", line);
+                }
+                value
+            })
+            .sum()
+    }
+}
+
+/// Iterator over the tokens of a line, see [`CalibrationDecoder::matches`].
+#[derive(Debug, Clone)]
+pub struct Matches<'d, 't> {
+    decoder: &'d CalibrationDecoder,
+    text: &'t [u8],
+    pos: usize,
+    state: usize,
+    output: usize,
+}
+
+impl Iterator for Matches<'_, '_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        loop {
+            let outputs = &self.decoder.states[self.state].outputs;
+            if let Some(&(value, len)) = outputs.get(self.output) {
+                self.output += 1;
+                return Some(Token {
+                    value,
+                    start: self.pos - len,
+                    end: self.pos,
+                });
+            }
+            let &byte = self.text.get(self.pos)?;
+            self.state = self.decoder.states[self.state].next[byte as usize] as usize;
+            self.pos += 1;
+            self.output = 0;
+        }
+    }
+}
+
+/// The calibration document.
+pub struct Puzzle {
+    document: String,
+}
+
+impl Solution for Puzzle {
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            document: String::from(input),
+        })
+    }
+
+    fn part1(&self) -> Result<u32, SolveError> {
+        Ok(Vocabulary::digits().decoder().total(&self.document))
+    }
+
+    fn part2(&self) -> Result<u32, SolveError> {
+        Ok(Vocabulary::english().decoder().total(&self.document))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+    const EXAMPLE_2: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE_1).unwrap().part1(), Ok(142));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE_2).unwrap().part2(), Ok(281));
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let decoder = Vocabulary::english().decoder();
+        let value = |line| decoder.decode(line).value();
+        assert_eq!(value("eightwo"), Some(82));
+        assert_eq!(value("oneight"), Some(18));
+        assert_eq!(value("sevenine"), Some(79));
+        assert_eq!(value("twone"), Some(21));
+    }
+
+    #[test]
+    fn token_offsets() {
+        let decoder = Vocabulary::english().decoder();
+        let calibration = decoder.decode("xtwone3four");
+        let first = calibration.first.unwrap();
+        let last = calibration.last.unwrap();
+        assert_eq!((first.value, first.start, first.end), (2, 1, 4));
+        assert_eq!((last.value, last.start, last.end), (4, 7, 11));
+        let values: Vec<u32> = decoder.matches("xtwone3four").map(|t| t.value).collect();
+        assert_eq!(values, [2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn line_without_digit() {
+        let decoder = Vocabulary::digits().decoder();
+        assert_eq!(decoder.decode("eightwo"), Calibration::default());
+        assert_eq!(decoder.decode("eightwo").value(), None);
+        assert_eq!(decoder.total("ab\n1x\n"), 11);
+    }
+
+    #[test]
+    fn vocabularies() {
+        let value = |vocabulary: Vocabulary, line| vocabulary.decoder().decode(line).value();
+        assert_eq!(value(Vocabulary::english(), "zeroxone"), Some(11));
+        assert_eq!(value(Vocabulary::extended(), "zeroxone"), Some(1));
+        assert_eq!(value(Vocabulary::extended(), "seventeen"), Some(17));
+        assert_eq!(value(Vocabulary::extended(), "twelvex3"), Some(13));
+        assert_eq!(value(Vocabulary::german(), "xdreiundvierzigfünf"), Some(35));
+        assert_eq!(value(Vocabulary::czech(), "šestnáctdvě"), Some(12));
+        assert_eq!(value(Vocabulary::digits(), "a0b"), Some(0));
+        for name in Vocabulary::PRESETS {
+            assert!(Vocabulary::preset(name).is_some(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn vocabulary_file() {
+        let vocabulary = Vocabulary::parse("# roman\n\n  I = 1\nV=5\n").unwrap();
+        assert_eq!(vocabulary.words().collect::<Vec<_>>(), [("I", 1), ("V", 5)]);
+        assert_eq!(vocabulary.decoder().decode("xVIx").value(), Some(51));
+
+        let err = Vocabulary::parse("I = 1\nV = five").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 5, "five"));
+        let err = Vocabulary::parse("I 1").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+
+    #[test]
+    fn explain_marks_tokens() {
+        let decoder = Vocabulary::english().decoder();
+        let explain = |line| decoder.decode(line).explain(line, Highlight::Plain);
+        assert_eq!(explain("xtwone3four"), " 24  x[two]ne3{four}");
+        assert_eq!(explain("treb7uchet"), " 77  treb[7]uchet");
+        assert_eq!(explain("eightwo"), " 82  [eigh{t]wo}");
+        assert_eq!(explain("abc"), "  -  abc  <- no number");
+        let line = "1x2";
+        assert_eq!(
+            decoder.decode(line).explain(line, Highlight::Ansi),
+            " 12  \x1b[1;32m1\x1b[0mx\x1b[1;34m2\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn prefers_longest_token_at_same_start() {
+        let decoder = CalibrationDecoder::new([("seven", 7), ("seventeen", 17), ("teen", 0)]);
+        let calibration = decoder.decode("seventeen");
+        assert_eq!(calibration.first.unwrap().value, 17);
+        assert_eq!(calibration.last.unwrap().value, 0);
+    }
+}
@@ -0,0 +1,506 @@
+//! Day 2: Cube Conundrum
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Display;
+
+use aoc_common::{
+    parse::{self, Line},
+    ParseError, Solution, SolveError,
+};
+
+use tokens::{Kind, Tokens};
+
+mod tokens;
+
+/// Some cubes of a single colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cubes {
+    pub count: u32,
+    pub colour: String,
+}
+
+/// One handful of cubes shown by the elf.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Draw {
+    pub cubes: Vec<Cubes>,
+}
+
+impl Draw {
+    /// Cubes of `colour` in this draw.
+    pub fn count(&self, colour: &str) -> u64 {
+        self.cubes
+            .iter()
+            .filter(|cubes| cubes.colour == colour)
+            .map(|cubes| u64::from(cubes.count))
+            .sum()
+    }
+
+    /// Cubes of any colour in this draw.
+    pub fn total(&self) -> u64 {
+        self.cubes.iter().map(|cubes| u64::from(cubes.count)).sum()
+    }
+}
+
+impl Display for Draw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, cubes) in self.cubes.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", cubes.count, cubes.colour)?;
+        }
+        Ok(())
+    }
+}
+
+/// How many cubes of each colour a bag holds, colours it does not mention
+/// having none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bag {
+    cubes: BTreeMap<String, u64>,
+}
+
+impl Bag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of `colour` cubes.
+    pub fn with(mut self, colour: &str, count: u64) -> Self {
+        self.cubes.insert(String::from(colour), count);
+        self
+    }
+
+    pub fn count(&self, colour: &str) -> u64 {
+        self.cubes.get(colour).copied().unwrap_or(0)
+    }
+
+    /// Parses `colour=count` entries separated by commas, as in
+    /// `red=12, green=13, blue=14`.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let line = Line::new(1, text);
+        let mut bag = Self::new();
+        for entry in text.split(',') {
+            let (colour, count) = line.split_once(entry, "=")?;
+            let colour = colour.trim();
+            if colour.is_empty() {
+                return Err(line.error(colour, "expected a colour before `=`"));
+            }
+            bag = bag.with(colour, line.parse(count.trim(), "a cube count")?);
+        }
+        Ok(bag)
+    }
+
+    /// The first colour of `draw` showing more cubes than this bag holds.
+    fn violation<'a>(&self, draw: &'a Draw) -> Option<Violation<'a>> {
+        draw.cubes.iter().find_map(|cubes| {
+            let max = self.count(&cubes.colour);
+            let count = draw.count(&cubes.colour);
+            (count > max).then_some(Violation {
+                draw: None,
+                limit: Limit::Colour(&cubes.colour),
+                count,
+                max,
+            })
+        })
+    }
+
+    /// Colours with their counts, in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.cubes
+            .iter()
+            .map(|(colour, &count)| (colour.as_str(), count))
+    }
+}
+
+impl Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (colour, count)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, colour)?;
+        }
+        Ok(())
+    }
+}
+
+/// The limit a game broke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit<'a> {
+    /// More cubes of a colour in one draw than the bag holds.
+    Colour(&'a str),
+    /// More cubes in one draw than allowed per draw.
+    Draw,
+    /// More cubes needed in total than the bag holds.
+    Total,
+}
+
+impl Display for Limit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Colour(colour) => write!(f, "{}", colour),
+            Limit::Draw => write!(f, "cubes in one draw"),
+            Limit::Total => write!(f, "cubes in total"),
+        }
+    }
+}
+
+/// Where a game breaks a constraint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation<'a> {
+    /// Index of the offending draw in [`Game::draws`], `None` for limits on
+    /// the whole game.
+    pub draw: Option<usize>,
+    pub limit: Limit<'a>,
+    pub count: u64,
+    pub max: u64,
+}
+
+impl Display for Violation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(draw) = self.draw {
+            write!(f, "draw {}: ", draw + 1)?;
+        }
+        write!(f, "{} {}, at most {}", self.count, self.limit, self.max)
+    }
+}
+
+/// Limits a game has to respect, all optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// Cubes of each colour any draw may show.
+    pub bag: Option<Bag>,
+    /// Cubes the bag holds in total, compared against [`Game::minimum_bag`].
+    pub total: Option<u64>,
+    /// Cubes any single draw may show.
+    pub per_draw: Option<u64>,
+}
+
+impl Constraints {
+    /// The first constraint `game` breaks, checking the draws in order
+    /// before the total.
+    pub fn violation<'a>(&self, game: &'a Game) -> Option<Violation<'a>> {
+        for (i, draw) in game.draws.iter().enumerate() {
+            if let Some(violation) = self.bag.as_ref().and_then(|bag| bag.violation(draw)) {
+                return Some(Violation {
+                    draw: Some(i),
+                    ..violation
+                });
+            }
+            if let Some(max) = self.per_draw.filter(|&max| draw.total() > max) {
+                return Some(Violation {
+                    draw: Some(i),
+                    limit: Limit::Draw,
+                    count: draw.total(),
+                    max,
+                });
+            }
+        }
+        let needed = game.minimum_bag().iter().map(|(_, count)| count).sum();
+        self.total.filter(|&max| needed > max).map(|max| Violation {
+            draw: None,
+            limit: Limit::Total,
+            count: needed,
+            max,
+        })
+    }
+}
+
+/// A game with every draw in the order shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<Draw>,
+}
+
+impl Game {
+    /// Parses `Game <id>: <count> <colour>, ...; ...` with any whitespace
+    /// between the tokens.
+    pub fn parse(line: &Line) -> Result<Self, ParseError> {
+        let mut tokens = Tokens::new(line)?;
+        let game = tokens.expect(Kind::Word, "`Game`")?;
+        if game != "Game" {
+            return Err(line.error(game, "expected `Game`"));
+        }
+        let id = tokens.expect(Kind::Number, "a game id")?;
+        let id = line.parse(id, "a game id")?;
+        tokens.expect(Kind::Colon, "`:`")?;
+
+        let mut draws = Vec::new();
+        loop {
+            let mut cubes = Vec::new();
+            loop {
+                let count = tokens.expect(Kind::Number, "a cube count")?;
+                let colour = tokens.expect(Kind::Word, "a colour")?;
+                cubes.push(Cubes {
+                    count: line.parse(count, "a cube count")?,
+                    colour: String::from(colour),
+                });
+                if tokens.eat(Kind::Comma).is_none() {
+                    break;
+                }
+            }
+            draws.push(Draw { cubes });
+            if tokens.eat(Kind::Semicolon).is_none() {
+                break;
+            }
+        }
+        if !tokens.at_end() {
+            return Err(tokens.unexpected("expected `,`, `;` or the end of the line"));
+        }
+        Ok(Self { id, draws })
+    }
+
+    /// The fewest cubes of each colour the game could have been played with.
+    /// A colour named twice in one draw counts both times, like
+    /// [`Game::is_possible`] does.
+    pub fn minimum_bag(&self) -> Bag {
+        let mut bag = Bag::new();
+        for draw in self.draws.iter() {
+            for cubes in draw.cubes.iter() {
+                let count = bag.count(&cubes.colour).max(draw.count(&cubes.colour));
+                bag = bag.with(&cubes.colour, count);
+            }
+        }
+        bag
+    }
+
+    /// Whether every draw fits in `bag`.
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.draws.iter().all(|draw| bag.violation(draw).is_none())
+    }
+
+    /// Product of the fewest red, green and blue cubes needed, `None` if it
+    /// overflows a `u64`.
+    pub fn power(&self) -> Option<u64> {
+        let bag = self.minimum_bag();
+        ["red", "green", "blue"]
+            .iter()
+            .try_fold(1u64, |power, colour| power.checked_mul(bag.count(colour)))
+    }
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (i, draw) in self.draws.iter().enumerate() {
+            let separator = if i > 0 { ";" } else { "" };
+            write!(f, "{} {}", separator, draw)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every game of the record.
+pub struct Puzzle {
+    games: Vec<Game>,
+}
+
+impl Puzzle {
+    pub fn games(&self) -> &[Game] {
+        &self.games
+    }
+}
+
+impl Solution for Puzzle {
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let games = parse::lines(input)
+            .filter(|line| !line.text.trim().is_empty())
+            .map(|line| Game::parse(&line))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { games })
+    }
+
+    fn part1(&self) -> Result<u64, SolveError> {
+        let bag = Bag::new()
+            .with("red", 12)
+            .with("green", 13)
+            .with("blue", 14);
+        let sum = self
+            .games
+            .iter()
+            .filter(|game| {
+                let possible = game.is_possible(&bag);
+                log::trace!("{} | {} {}", game, game.minimum_bag(), possible);
+                possible
+            })
+            .map(|game| u64::from(game.id))
+            .sum();
+        Ok(sum)
+    }
+
+    fn part2(&self) -> Result<u64, SolveError> {
+        self.games
+            .iter()
+            .try_fold(0u64, |sum, game| sum.checked_add(game.power()?))
+            .ok_or_else(|| SolveError::new("the powers overflow a u64"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part1(), Ok(8));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part2(), Ok(2286));
+    }
+
+    #[test]
+    fn keeps_every_draw() {
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        let game = &puzzle.games()[2];
+        assert_eq!(game.draws.len(), 3);
+        assert_eq!(game.draws[1].count("green"), 13);
+        assert_eq!(game.draws[0].total(), 34);
+        assert_eq!(game.minimum_bag().to_string(), "6 blue, 13 green, 20 red");
+        assert_eq!(game.to_string(), EXAMPLE.lines().nth(2).unwrap());
+    }
+
+    #[test]
+    fn violating_draw() {
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        let bag = Bag::parse("red=12, green=13,blue = 14").unwrap();
+        let constraints = Constraints {
+            bag: Some(bag.clone()),
+            ..Constraints::default()
+        };
+        let violation = constraints.violation(&puzzle.games()[2]).unwrap();
+        assert_eq!(violation.draw, Some(0));
+        assert_eq!(violation.limit, Limit::Colour("red"));
+        assert_eq!(violation.to_string(), "draw 1: 20 red, at most 12");
+        assert!(puzzle.games()[0].is_possible(&bag));
+        assert!(constraints.violation(&puzzle.games()[0]).is_none());
+    }
+
+    #[test]
+    fn cube_caps() {
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        let game = &puzzle.games()[0];
+        let per_draw = Constraints {
+            per_draw: Some(8),
+            ..Constraints::default()
+        };
+        let violation = per_draw.violation(game).unwrap();
+        assert_eq!(
+            violation.to_string(),
+            "draw 2: 9 cubes in one draw, at most 8"
+        );
+
+        let total = Constraints {
+            total: Some(11),
+            ..Constraints::default()
+        };
+        let violation = total.violation(game).unwrap();
+        assert_eq!((violation.draw, violation.limit), (None, Limit::Total));
+        assert_eq!(violation.to_string(), "12 cubes in total, at most 11");
+        assert!(Constraints::default().violation(game).is_none());
+    }
+
+    #[test]
+    fn repeated_colour() {
+        let puzzle = Puzzle::parse("Game 1: 3 red, 4 red, 1 blue; 5 red, 2 green").unwrap();
+        let game = &puzzle.games()[0];
+        let bag = game.minimum_bag();
+        assert_eq!(
+            bag,
+            Bag::new().with("red", 7).with("blue", 1).with("green", 2)
+        );
+        assert!(game.is_possible(&bag));
+        assert!(!game.is_possible(&bag.clone().with("red", 6)));
+        assert_eq!(game.power(), Some(14));
+    }
+
+    #[test]
+    fn huge_counts() {
+        let puzzle = Puzzle::parse("Game 1: 4000000000 red, 4000000000 red").unwrap();
+        let game = &puzzle.games()[0];
+        assert_eq!(game.draws[0].count("red"), 8_000_000_000);
+        assert_eq!(game.minimum_bag().count("red"), 8_000_000_000);
+        assert_eq!(puzzle.part1(), Ok(0));
+
+        let puzzle = Puzzle::parse("Game 4000000000: 2000 red, 3000 green, 4000 blue").unwrap();
+        assert_eq!(puzzle.part2(), Ok(24_000_000_000));
+        let puzzle =
+            Puzzle::parse("Game 1: 4000000000 red, 4000000000 green, 4000000000 blue").unwrap();
+        assert!(puzzle.part2().is_err());
+    }
+
+    #[test]
+    fn malformed_bag() {
+        let err = Bag::parse("red=12,green").unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (13, "expected `=`"));
+        let err = Bag::parse("red=lots").unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (5, "lots"));
+    }
+
+    #[test]
+    fn tolerates_whitespace() {
+        let puzzle = Puzzle::parse("Game  7 :3 blue ;4 red,\t1 light-green  \n\n").unwrap();
+        let game = &puzzle.games()[0];
+        assert_eq!(game.id, 7);
+        assert_eq!(game.to_string(), "Game 7: 3 blue; 4 red, 1 light-green");
+    }
+
+    #[test]
+    fn malformed_games() {
+        let error = |input| {
+            let err = Puzzle::parse(input).err().unwrap();
+            (err.column, err.token, err.message)
+        };
+        let expected = |column, token: &str, message: &str| {
+            (column, String::from(token), String::from(message))
+        };
+        assert_eq!(
+            error("game 1: 3 red"),
+            expected(1, "game", "expected `Game`")
+        );
+        assert_eq!(
+            error("Game x: 3 red"),
+            expected(6, "x", "expected a game id")
+        );
+        assert_eq!(error("Game 1 3 red"), expected(8, "3", "expected `:`"));
+        assert_eq!(error("Game 1: 3"), expected(10, "", "expected a colour"));
+        assert_eq!(
+            error("Game 1: 3 red;"),
+            expected(15, "", "expected a cube count")
+        );
+        assert_eq!(
+            error("Game 1: 3 red 4 blue"),
+            expected(15, "4", "expected `,`, `;` or the end of the line")
+        );
+        assert_eq!(
+            error("Game 1: 3 red!"),
+            expected(14, "!", "expected a word, a number, `:`, `;` or `,`")
+        );
+        assert_eq!(
+            error("Game 1: 99999999999 red"),
+            expected(9, "99999999999", "expected a cube count")
+        );
+    }
+
+    #[test]
+    fn any_colour() {
+        let puzzle = Puzzle::parse("Game 1: 3 blue\nGame 2: 4 pink, 1 blue").unwrap();
+        let game = &puzzle.games()[1];
+        assert_eq!(game.minimum_bag().count("pink"), 4);
+        assert_eq!(game.power(), Some(0));
+        assert_eq!(puzzle.part1(), Ok(1));
+    }
+}
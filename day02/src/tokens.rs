@@ -0,0 +1,106 @@
+//! Tokens of a game record line.
+
+use aoc_common::{parse::Line, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Word,
+    Number,
+    Colon,
+    Semicolon,
+    Comma,
+}
+
+/// A token, `text` being a slice of the line so errors can point at it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: Kind,
+    pub text: &'a str,
+}
+
+/// Splits `line` into words, numbers and punctuation, skipping any
+/// whitespace between them.
+pub fn tokenize<'a>(line: &Line<'a>) -> Result<Vec<Token<'a>>, ParseError> {
+    let text = line.text;
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            ':' => Kind::Colon,
+            ';' => Kind::Semicolon,
+            ',' => Kind::Comma,
+            c if c.is_whitespace() => continue,
+            c if c.is_ascii_digit() => Kind::Number,
+            c if c.is_alphabetic() => Kind::Word,
+            _ => {
+                let token = &text[start..start + c.len_utf8()];
+                return Err(line.error(token, "expected a word, a number, `:`, `;` or `,`"));
+            }
+        };
+        let mut end = start + c.len_utf8();
+        if kind == Kind::Number || kind == Kind::Word {
+            let continues = |c: char| match kind {
+                Kind::Number => c.is_ascii_digit(),
+                _ => c.is_alphanumeric() || c == '-' || c == '_',
+            };
+            while let Some(&(i, c)) = chars.peek() {
+                if !continues(c) {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+        }
+        tokens.push(Token {
+            kind,
+            text: &text[start..end],
+        });
+    }
+    Ok(tokens)
+}
+
+/// Cursor over the tokens of a line.
+pub struct Tokens<'l, 'a> {
+    line: &'l Line<'a>,
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'l, 'a> Tokens<'l, 'a> {
+    pub fn new(line: &'l Line<'a>) -> Result<Self, ParseError> {
+        Ok(Self {
+            line,
+            tokens: tokenize(line)?,
+            pos: 0,
+        })
+    }
+
+    /// Takes the next token if it is of `kind`.
+    pub fn eat(&mut self, kind: Kind) -> Option<&'a str> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .filter(|token| token.kind == kind)?;
+        self.pos += 1;
+        Some(token.text)
+    }
+
+    /// Takes the next token, which has to be of `kind`, described as `what`
+    /// in the error otherwise.
+    pub fn expect(&mut self, kind: Kind, what: &str) -> Result<&'a str, ParseError> {
+        self.eat(kind)
+            .ok_or_else(|| self.unexpected(&format!("expected {}", what)))
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.pos == self.tokens.len()
+    }
+
+    /// Error pointing at the next token, or at the end of the line.
+    pub fn unexpected(&self, message: &str) -> ParseError {
+        match self.tokens.get(self.pos) {
+            Some(token) => self.line.error(token.text, message),
+            None => self.line.missing(self.line.text, message),
+        }
+    }
+}
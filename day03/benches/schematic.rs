@@ -0,0 +1,66 @@
+//! Parsing and solving generated schematics of growing size, showing that
+//! the work grows linearly with the input.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use aoc_common::{random::XorShift, Solution};
+use day03::Puzzle;
+
+/// A `size` by `size` schematic, roughly as dense in numbers and symbols as
+/// the puzzle input.
+fn generate(size: usize) -> String {
+    const SYMBOLS: &[u8] = b"*#+$/=%@&-";
+    let mut rng = XorShift::new();
+    let mut input = String::with_capacity((size + 1) * size);
+    for _ in 0..size {
+        let mut x = 0;
+        while x < size {
+            let roll = rng.below(100);
+            if roll < 12 {
+                let digits = (1 + rng.below(3)) as usize;
+                for _ in 0..digits.min(size - x) {
+                    input.push(char::from(b'0' + (rng.below(10)) as u8));
+                }
+                x += digits.min(size - x);
+                if x < size {
+                    input.push('.');
+                    x += 1;
+                }
+                continue;
+            }
+            if roll < 17 {
+                input.push(char::from(
+                    SYMBOLS[(rng.below(SYMBOLS.len() as u64)) as usize],
+                ));
+            } else {
+                input.push('.');
+            }
+            x += 1;
+        }
+        input.push('\n');
+    }
+    input
+}
+
+fn schematic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("schematic");
+    group.sample_size(20);
+    for size in [140, 500, 1000, 2000] {
+        let input = generate(size);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("parse", size), &input, |b, input| {
+            b.iter(|| Puzzle::parse(black_box(input)).unwrap())
+        });
+        let puzzle = Puzzle::parse(&input).unwrap();
+        group.bench_with_input(BenchmarkId::new("part1", size), &puzzle, |b, puzzle| {
+            b.iter(|| puzzle.part1())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", size), &puzzle, |b, puzzle| {
+            b.iter(|| puzzle.part2())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, schematic);
+criterion_main!(benches);
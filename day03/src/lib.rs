@@ -0,0 +1,547 @@
+//! Day 3: Gear Ratios
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::ops::RangeBounds;
+
+use aoc_common::{
+    geometry::{self, Direction, Grid, Point},
+    paint,
+    parse::{self, Line},
+    Highlight, ParseError, Solution, SolveError,
+};
+
+/// Position of a byte in the schematic, `x` along the line and `y` the line.
+pub type Pos = Point<u32>;
+
+/// Half-open rectangle of schematic positions.
+pub type Area = geometry::Area<u32>;
+
+/// A number in the schematic, the area its digits cover and the positions
+/// surrounding it.
+#[derive(Debug, Default, Clone)]
+pub struct Component {
+    pub value: u32,
+    pub area: Area,
+    pub neigbours_pos: Vec<Pos>,
+}
+
+impl Component {
+    pub fn constrain_area(&mut self, area: Area) {
+        self.neigbours_pos.retain(|&x| area.contains(x));
+    }
+
+    pub fn new(value: u32, area: Area) -> Self {
+        Self {
+            value,
+            area,
+            neigbours_pos: area.surrounding(),
+        }
+    }
+}
+
+/// A symbol character and where it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Symbol {
+    pub pos: Pos,
+    pub char: u8,
+}
+
+/// A number with every symbol touching it.
+#[derive(Debug, Clone)]
+pub struct Part<'a> {
+    pub component: &'a Component,
+    pub symbols: Vec<Symbol>,
+}
+
+impl Part<'_> {
+    /// Whether the number counts as a part number.
+    pub fn is_counted(&self) -> bool {
+        !self.symbols.is_empty()
+    }
+}
+
+/// A symbol with the numbers touching it.
+#[derive(Debug, Clone)]
+pub struct SymbolGroup<'a> {
+    pub symbol: Symbol,
+    pub components: Vec<&'a Component>,
+}
+
+impl SymbolGroup<'_> {
+    pub fn values(&self) -> impl Iterator<Item = u32> + '_ {
+        self.components.iter().map(|component| component.value)
+    }
+
+    /// Product of the numbers, the gear ratio for gears, or `None` if it
+    /// does not fit a `u64`.
+    pub fn ratio(&self) -> Option<u64> {
+        self.values()
+            .try_fold(1u64, |product, value| product.checked_mul(u64::from(value)))
+    }
+
+    pub fn sum(&self) -> u64 {
+        self.values().map(u64::from).sum()
+    }
+}
+
+/// Which symbols are gears: the character and how many numbers they touch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gear {
+    pub symbol: u8,
+    pub arity: usize,
+}
+
+impl Gear {
+    /// A `*` touching exactly two numbers.
+    pub const PUZZLE: Self = Self {
+        symbol: b'*',
+        arity: 2,
+    };
+}
+
+/// What a position of the schematic holds.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    #[default]
+    Empty,
+    Symbol(u8),
+    /// A digit of the component with this index.
+    Number(usize),
+}
+
+/// Numbers and symbols of an engine schematic.
+#[derive(Debug, Default, Clone)]
+pub struct Schematic {
+    components: Vec<Component>,
+    symbols: Vec<Pos>,
+    /// What each position holds so that neighbour lookups take constant
+    /// time, short lines padded with `Empty`.
+    cells: Grid<Cell>,
+    area: Area,
+}
+
+impl Schematic {
+    /// Drops neighbour positions falling outside of the schematic.
+    pub fn constrain_area(&mut self) {
+        for component in self.components.iter_mut() {
+            component.constrain_area(self.area)
+        }
+    }
+
+    pub fn parse_line(&mut self, line: &Line) -> Result<(), ParseError> {
+        let text = line.text;
+        self.area.end.x = self.area.end.x.max(text.len() as u32);
+        let y: u32 = self.area.end.y;
+        self.area.end.y += 1;
+
+        let bytes = text.as_bytes();
+        let mut row = vec![Cell::Empty; bytes.len()];
+        let mut i = 0;
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_graphic() {
+                let c = text[i..].chars().next().unwrap();
+                let token = &text[i..i + c.len_utf8()];
+                return Err(line.error(token, "expected a digit, `.` or a symbol"));
+            } else if bytes[i] == 46 {
+                i += 1;
+            } else if !bytes[i].is_ascii_digit() {
+                self.symbols.push(Pos::new(i as u32, y));
+                row[i] = Cell::Symbol(bytes[i]);
+                i += 1;
+            } else {
+                let x = i as u32;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let val: u32 = line.parse(&text[x as usize..i], "a part number")?;
+                let pos = Pos::new(x, y);
+                let end = Pos::new(i as u32, y + 1);
+                let new_component = Component::new(val, Area::new(pos, end));
+                row[x as usize..i].fill(Cell::Number(self.components.len()));
+                self.components.push(new_component);
+            }
+        }
+        self.cells.push_row(row, Cell::Empty);
+        Ok(())
+    }
+
+    pub fn components(&self) -> &[Component] {
+        &self.components
+    }
+
+    pub fn symbols(&self) -> &[Pos] {
+        &self.symbols
+    }
+
+    pub fn area(&self) -> Area {
+        self.area
+    }
+
+    /// What `pos` holds, `Empty` outside of the schematic.
+    pub fn cell(&self, pos: Pos) -> Cell {
+        self.cells.get(pos).copied().unwrap_or_default()
+    }
+
+    /// Indices of the distinct components touching `pos`, in reading order.
+    fn adjacent_components(&self, pos: Pos) -> Vec<usize> {
+        let pos = Point::new(pos.x as usize, pos.y as usize);
+        let mut adjacent: Vec<usize> = Vec::new();
+        for (_, &cell) in self.cells.neighbours(pos, &Direction::ALL) {
+            if let Cell::Number(i) = cell {
+                if !adjacent.contains(&i) {
+                    adjacent.push(i);
+                }
+            }
+        }
+        adjacent.sort_unstable();
+        adjacent
+    }
+
+    pub fn new() -> Self {
+        Self {
+            components: Vec::new(),
+            symbols: Vec::new(),
+            cells: Grid::default(),
+            area: Area::default(),
+        }
+    }
+
+    /// Symbols touching `component`, in reading order.
+    pub fn adjacent_symbols(&self, component: &Component) -> Vec<Symbol> {
+        let mut symbols: Vec<Symbol> = component
+            .neigbours_pos
+            .iter()
+            .filter_map(|&pos| match self.cell(pos) {
+                Cell::Symbol(char) => Some(Symbol { pos, char }),
+                _ => None,
+            })
+            .collect();
+        symbols.sort_by_key(|symbol| (symbol.pos.y, symbol.pos.x));
+        symbols
+    }
+
+    /// Every number with the symbols touching it.
+    pub fn parts(&self) -> impl Iterator<Item = Part<'_>> {
+        self.components.iter().map(|component| Part {
+            component,
+            symbols: self.adjacent_symbols(component),
+        })
+    }
+
+    /// Sum of the numbers touching at least one symbol, each counted once.
+    pub fn part_numbers_sum(&self) -> u64 {
+        self.parts()
+            .filter(|part| {
+                log::trace!("{} next to {:?}", part.component.value, part.symbols);
+                part.is_counted()
+            })
+            .map(|part| u64::from(part.component.value))
+            .sum()
+    }
+
+    /// Every `symbol` touching a number of numbers within `arity`, with
+    /// those numbers, in reading order.
+    ///
+    /// ```
+    /// # use aoc_common::Solution;
+    /// let puzzle = day03::Puzzle::parse("2..\n.*.\n4.*\n..3").unwrap();
+    /// let ratios: Vec<u64> = puzzle
+    ///     .schematic()
+    ///     .symbol_groups(b'*', 2..=2)
+    ///     .filter_map(|group| group.ratio())
+    ///     .collect();
+    /// assert_eq!(ratios, [8]);
+    /// ```
+    pub fn symbol_groups(
+        &self,
+        symbol: u8,
+        arity: impl RangeBounds<usize>,
+    ) -> impl Iterator<Item = SymbolGroup<'_>> {
+        self.symbol_groups_where(move |char| char == symbol, arity)
+    }
+
+    /// Like [`Schematic::symbol_groups`] for every symbol `class` accepts.
+    pub fn symbol_groups_where(
+        &self,
+        class: impl Fn(u8) -> bool,
+        arity: impl RangeBounds<usize>,
+    ) -> impl Iterator<Item = SymbolGroup<'_>> {
+        self.symbols.iter().filter_map(move |&pos| {
+            let Cell::Symbol(char) = self.cell(pos) else {
+                return None;
+            };
+            if !class(char) {
+                return None;
+            }
+            let components: Vec<&Component> = self
+                .adjacent_components(pos)
+                .into_iter()
+                .map(|i| &self.components[i])
+                .collect();
+            log::trace!("{} at {}: {} numbers", char as char, pos, components.len());
+            arity.contains(&components.len()).then_some(SymbolGroup {
+                symbol: Symbol { pos, char },
+                components,
+            })
+        })
+    }
+
+    /// Sum over every `gear` of the product of the numbers it touches.
+    pub fn gear_ratios(&self, gear: Gear) -> u64 {
+        self.symbol_groups(gear.symbol, gear.arity..=gear.arity)
+            .filter_map(|group| {
+                let ratio = group.ratio();
+                if ratio.is_none() {
+                    log::warn!("skipping gear at {}, its ratio overflows", group.symbol.pos);
+                }
+                ratio
+            })
+            .sum()
+    }
+
+    /// Redraws the schematic marking part numbers, numbers touching no
+    /// symbol, `gear`s and the other symbols.
+    ///
+    /// In colour they are green, red, bold yellow and blue, otherwise part
+    /// numbers read `[467]`, other numbers `(114)` and gears `<*>`.
+    pub fn render(&self, gear: Gear, highlight: Highlight) -> String {
+        let counted: Vec<bool> = self.parts().map(|part| part.is_counted()).collect();
+        let gears: HashSet<Pos> = self
+            .symbol_groups(gear.symbol, gear.arity..=gear.arity)
+            .map(|group| group.symbol.pos)
+            .collect();
+        let mut out = String::new();
+        for (y, row) in self.cells.rows().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                let pos = Pos::new(x as u32, y as u32);
+                let (text, marks) = match row[x] {
+                    Cell::Empty => (String::from("."), None),
+                    Cell::Symbol(char) if gears.contains(&pos) => {
+                        (String::from(char as char), Some(("1;33", "<", ">")))
+                    }
+                    Cell::Symbol(char) => (String::from(char as char), Some(("34", "", ""))),
+                    Cell::Number(i) => {
+                        let area = self.components[i].area;
+                        let width = (area.end.x - area.start.x) as usize;
+                        x = area.end.x as usize - 1;
+                        let text = format!("{:0width$}", self.components[i].value);
+                        if counted[i] {
+                            (text, Some(("32", "[", "]")))
+                        } else {
+                            (text, Some(("31", "(", ")")))
+                        }
+                    }
+                };
+                match (marks, highlight) {
+                    (Some((code, _, _)), Highlight::Ansi) => out += &paint(code, &text),
+                    (Some((_, open, close)), Highlight::Plain) => {
+                        out += open;
+                        out += &text;
+                        out += close;
+                    }
+                    (None, _) => out += &text,
+                }
+                x += 1;
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// The schematic with the part numbers and gears of the puzzle marked by
+/// brackets, see [`Schematic::render`].
+impl Display for Schematic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(Gear::PUZZLE, Highlight::Plain))
+    }
+}
+
+/// The parsed engine schematic.
+pub struct Puzzle {
+    schematic: Schematic,
+}
+
+impl Puzzle {
+    pub fn schematic(&self) -> &Schematic {
+        &self.schematic
+    }
+}
+
+impl Solution for Puzzle {
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut schematic = Schematic::new();
+        for line in parse::lines(input) {
+            schematic.parse_line(&line)?;
+        }
+        schematic.constrain_area();
+        Ok(Self { schematic })
+    }
+
+    fn part1(&self) -> Result<u64, SolveError> {
+        Ok(self.schematic.part_numbers_sum())
+    }
+
+    fn part2(&self) -> Result<u64, SolveError> {
+        Ok(self.schematic.gear_ratios(Gear::PUZZLE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part1(), Ok(4361));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part2(), Ok(467835));
+    }
+
+    #[test]
+    fn cell_index() {
+        let puzzle = Puzzle::parse("467..\n...*.\n..35").unwrap();
+        let schematic = puzzle.schematic();
+        assert_eq!(schematic.cell(Pos::new(1, 0)), Cell::Number(0));
+        assert_eq!(schematic.cell(Pos::new(3, 1)), Cell::Symbol(b'*'));
+        assert_eq!(schematic.cell(Pos::new(3, 2)), Cell::Number(1));
+        assert_eq!(schematic.cell(Pos::new(4, 2)), Cell::Empty);
+        assert_eq!(schematic.cell(Pos::new(0, 9)), Cell::Empty);
+        assert_eq!(schematic.area().end, Pos::new(5, 3));
+        assert_eq!(puzzle.part2(), Ok(467 * 35));
+    }
+
+    #[test]
+    fn counts_numbers_once() {
+        let puzzle = Puzzle::parse("#..\n12.\n..$").unwrap();
+        let parts: Vec<Part> = puzzle.schematic().parts().collect();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            parts[0].symbols,
+            [
+                Symbol {
+                    pos: Pos::new(0, 0),
+                    char: b'#'
+                },
+                Symbol {
+                    pos: Pos::new(2, 2),
+                    char: b'$'
+                }
+            ]
+        );
+        assert_eq!(puzzle.part1(), Ok(12));
+    }
+
+    #[test]
+    fn configurable_gears() {
+        let puzzle = Puzzle::parse("2.3\n.#.\n4..").unwrap();
+        let schematic = puzzle.schematic();
+        let gear = |arity| Gear {
+            symbol: b'#',
+            arity,
+        };
+        assert_eq!(schematic.gear_ratios(gear(3)), 24);
+        assert_eq!(schematic.gear_ratios(gear(2)), 0);
+        assert_eq!(schematic.gear_ratios(Gear::PUZZLE), 0);
+    }
+
+    #[test]
+    fn ragged_lines() {
+        let puzzle = Puzzle::parse("12\n..*\n\n..3").unwrap();
+        let schematic = puzzle.schematic();
+        assert_eq!(schematic.cell(Pos::new(2, 0)), Cell::Empty);
+        assert_eq!(schematic.cell(Pos::new(2, 2)), Cell::Empty);
+        assert_eq!(schematic.cell(Pos::new(2, 3)), Cell::Number(1));
+        assert_eq!(schematic.part_numbers_sum(), 12);
+        assert_eq!(schematic.to_string(), "[12].\n..*\n...\n..(3)\n");
+    }
+
+    #[test]
+    fn large_part_numbers() {
+        let input = ["999999999#999999999"; 3].join("\n");
+        let puzzle = Puzzle::parse(&input).unwrap();
+        assert_eq!(puzzle.part1(), Ok(6 * 999_999_999));
+        // The `#` on the first line touches its own line and the next.
+        let group = puzzle.schematic().symbol_groups(b'#', ..).next().unwrap();
+        assert_eq!(group.sum(), 4 * 999_999_999);
+    }
+
+    #[test]
+    fn large_gear_ratios() {
+        let gear = Gear {
+            symbol: b'*',
+            arity: 4,
+        };
+        let puzzle = Puzzle::parse("999.999\n...*...\n999.999").unwrap();
+        assert_eq!(puzzle.schematic().gear_ratios(gear), 996_005_996_001);
+
+        // Six 9-digit numbers overflow even a u64 and are skipped.
+        let wide = "999999999.999999999\n999999999*999999999\n999999999.999999999";
+        let puzzle = Puzzle::parse(wide).unwrap();
+        let schematic = puzzle.schematic();
+        let group = schematic.symbol_groups(b'*', 6..).next().unwrap();
+        assert_eq!(group.ratio(), None);
+        let gear = Gear { arity: 6, ..gear };
+        assert_eq!(schematic.gear_ratios(gear), 0);
+    }
+
+    #[test]
+    fn symbol_groups() {
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        let schematic = puzzle.schematic();
+        let gears: Vec<(Pos, Vec<u32>)> = schematic
+            .symbol_groups(b'*', 2..=2)
+            .map(|group| (group.symbol.pos, group.values().collect()))
+            .collect();
+        assert_eq!(
+            gears,
+            [
+                (Pos::new(3, 1), vec![467, 35]),
+                (Pos::new(5, 8), vec![755, 598])
+            ]
+        );
+        assert_eq!(schematic.symbol_groups(b'*', 1..2).count(), 1);
+        assert_eq!(schematic.symbol_groups(b'*', ..).count(), 3);
+        let sums: u64 = schematic
+            .symbol_groups_where(|c| c != b'*', 1..)
+            .map(|group| group.sum())
+            .sum();
+        assert_eq!(sums, 633 + 592 + 664);
+    }
+
+    #[test]
+    fn render_markers() {
+        let puzzle =
+            Puzzle::parse("467..114..\n...*......\n..35..633.\n......#...\n007.......").unwrap();
+        assert_eq!(
+            puzzle.schematic().to_string(),
+            "[467]..(114)..
+...<*>......
+..[35]..[633].
+......#...
+(007).......
+"
+        );
+        let ansi = puzzle.schematic().render(Gear::PUZZLE, Highlight::Ansi);
+        assert!(ansi.starts_with("\x1b[32m467\x1b[0m..\x1b[31m114\x1b[0m..\n"));
+    }
+}
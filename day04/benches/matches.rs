@@ -0,0 +1,58 @@
+//! Counting the matches of generated cards with a [`NumberSet`] bitset
+//! against the `HashSet` intersection it replaced.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use aoc_common::random::XorShift;
+use day04::{matches, matches_hashed, NumberSet};
+
+/// `count` cards shaped like the puzzle input: 10 winning numbers and 25
+/// owned ones, all distinct and below 100.
+fn generate(count: usize) -> Vec<(Vec<u32>, Vec<u32>)> {
+    let mut rng = XorShift::new();
+    let mut distinct = move |len: usize| {
+        let mut seen = NumberSet::default();
+        let mut numbers = Vec::with_capacity(len);
+        while numbers.len() < len {
+            let number = rng.below(u64::from(NumberSet::CAPACITY)) as u32;
+            if seen.insert(number) {
+                numbers.push(number);
+            }
+        }
+        numbers
+    };
+    (0..count).map(|_| (distinct(10), distinct(25))).collect()
+}
+
+fn count(c: &mut Criterion) {
+    let cards = generate(200);
+    let mut group = c.benchmark_group("matches");
+    group.bench_with_input(
+        BenchmarkId::new("bitset", cards.len()),
+        &cards,
+        |b, cards| {
+            b.iter(|| {
+                black_box(cards)
+                    .iter()
+                    .map(|(winning, owned)| matches(winning, owned))
+                    .sum::<usize>()
+            })
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("hash_set", cards.len()),
+        &cards,
+        |b, cards| {
+            b.iter(|| {
+                black_box(cards)
+                    .iter()
+                    .map(|(winning, owned)| matches_hashed(winning, owned))
+                    .sum::<usize>()
+            })
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, count);
+criterion_main!(benches);
@@ -0,0 +1,452 @@
+//! Day 4: Scratchcards
+
+use std::collections::HashSet;
+
+use aoc_common::{
+    parse::{self, Line},
+    ParseError, Solution, SolveError,
+};
+
+/// Set of numbers below [`NumberSet::CAPACITY`], one bit each.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NumberSet(u128);
+
+impl NumberSet {
+    /// Scratchcard numbers have at most two digits.
+    pub const CAPACITY: u32 = 100;
+
+    /// Adds `number`, returning whether it was new.
+    ///
+    /// Panics if `number` is not below [`Self::CAPACITY`].
+    pub fn insert(&mut self, number: u32) -> bool {
+        assert!(number < Self::CAPACITY, "{} does not fit", number);
+        let bit = 1 << number;
+        let new = self.0 & bit == 0;
+        self.0 |= bit;
+        new
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        number < Self::CAPACITY && self.0 & 1 << number != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut set = Self::default();
+        for number in iter {
+            set.insert(number);
+        }
+        set
+    }
+}
+
+/// Matches between two lists of numbers below [`NumberSet::CAPACITY`].
+///
+/// Panics on larger numbers, see [`count_matches`] for any numbers.
+pub fn matches(winning: &[u32], owned: &[u32]) -> usize {
+    let winning: NumberSet = winning.iter().copied().collect();
+    let owned: NumberSet = owned.iter().copied().collect();
+    winning.intersection(&owned).len()
+}
+
+/// Same as [`matches`] for numbers of any size, kept as the baseline of the
+/// `matches` benchmark.
+pub fn matches_hashed(winning: &[u32], owned: &[u32]) -> usize {
+    let winning: HashSet<u32> = winning.iter().copied().collect();
+    let owned: HashSet<u32> = owned.iter().copied().collect();
+    winning.intersection(&owned).count()
+}
+
+/// Matches between two lists of numbers, with a [`NumberSet`] when they all
+/// fit one and a `HashSet` otherwise.
+pub fn count_matches(winning: &[u32], owned: &[u32]) -> usize {
+    let small = |numbers: &[u32]| numbers.iter().all(|&n| n < NumberSet::CAPACITY);
+    if small(winning) && small(owned) {
+        matches(winning, owned)
+    } else {
+        matches_hashed(winning, owned)
+    }
+}
+
+/// One `Card N: winning | owned` line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcard {
+    id: u32,
+    winning: Vec<u32>,
+    owned: Vec<u32>,
+    /// Number of winning numbers we own, counted once on parsing.
+    matches: usize,
+}
+
+impl Scratchcard {
+    pub fn parse(line: &Line) -> Result<Self, ParseError> {
+        let (head, numbers) = line.split_once(line.text, ":")?;
+        let id = head
+            .strip_prefix("Card")
+            .ok_or_else(|| line.error(head, "expected `Card`"))?
+            .trim();
+        let (winning, owned) = line.split_once(numbers, "|")?;
+        let winning = line.numbers(winning, "a winning number")?;
+        let owned = line.numbers(owned, "a number")?;
+        Ok(Self {
+            id: line.parse(id, "a card id")?,
+            matches: count_matches(&winning, &owned),
+            winning,
+            owned,
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn winning(&self) -> &[u32] {
+        &self.winning
+    }
+
+    pub fn owned(&self) -> &[u32] {
+        &self.owned
+    }
+
+    /// Number of winning numbers we own.
+    pub fn matches(&self) -> usize {
+        self.matches
+    }
+
+    /// One point for the first match, doubled for every further one, `None`
+    /// past 128 matches where the points overflow a `u128`.
+    pub fn points(&self) -> Option<u128> {
+        match self.matches {
+            0 => Some(0),
+            matches => 1u128.checked_shl(u32::try_from(matches - 1).ok()?),
+        }
+    }
+}
+
+/// The copies held of every card, played from the top of the table down.
+///
+/// Each card wins one copy of the next `matches` cards per copy held of it.
+/// Cards are added one at a time, so the table does not need to be known in
+/// advance; copies won past the last card are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct Pile {
+    /// Copies per card, including ones won for cards not added yet.
+    copies: Vec<u64>,
+    /// Matches per card added.
+    matches: Vec<usize>,
+}
+
+impl Pile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Plays every card of a table given its matches, `None` if the copies
+    /// of a card overflow a `u64`.
+    pub fn play(matches: impl IntoIterator<Item = usize>) -> Option<Self> {
+        let mut pile = Self::new();
+        for matches in matches {
+            pile.add(matches)?;
+        }
+        Some(pile)
+    }
+
+    /// Adds the next card with `matches` matches, returning the copies held
+    /// of it. When the copies won would overflow a `u64` the pile is left as
+    /// it was and `None` returned.
+    pub fn add(&mut self, matches: usize) -> Option<u64> {
+        let card = self.matches.len();
+        let end = card + 1 + matches;
+        if self.copies.len() < end {
+            self.copies.resize(end, 0);
+        }
+        let held = self.copies[card].checked_add(1)?;
+        if self.copies[card + 1..end]
+            .iter()
+            .any(|copies| copies.checked_add(held).is_none())
+        {
+            return None;
+        }
+        self.copies[card] = held;
+        for copies in &mut self.copies[card + 1..end] {
+            *copies += held;
+        }
+        self.matches.push(matches);
+        Some(held)
+    }
+
+    /// Copies held per card added so far.
+    pub fn copies(&self) -> &[u64] {
+        &self.copies[..self.matches.len()]
+    }
+
+    /// Copies of all cards together, `None` if they overflow a `u64`.
+    pub fn total(&self) -> Option<u64> {
+        self.copies()
+            .iter()
+            .try_fold(0u64, |total, &copies| total.checked_add(copies))
+    }
+
+    /// How every card got its copies, indexed like [`Pile::copies`].
+    pub fn cascade(&self) -> Vec<Cascade> {
+        let cards = self.matches.len();
+        let mut cascade: Vec<Cascade> = self
+            .matches
+            .iter()
+            .zip(self.copies())
+            .map(|(&matches, &copies)| Cascade {
+                matches,
+                copies,
+                won_from: Vec::new(),
+                depth: 1,
+                previous: None,
+            })
+            .collect();
+        for card in 0..cards {
+            let end = (card + 1 + cascade[card].matches).min(cards);
+            let depth = cascade[card].depth + 1;
+            for won in &mut cascade[card + 1..end] {
+                won.won_from.push(card);
+                if won.depth < depth {
+                    won.depth = depth;
+                    won.previous = Some(card);
+                }
+            }
+        }
+        cascade
+    }
+
+    /// The card holding the most copies, the first one on ties.
+    pub fn most_copied(&self) -> Option<(usize, u64)> {
+        self.copies()
+            .iter()
+            .copied()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, copies)| copies)
+    }
+
+    /// The longest chain of cards each winning a copy of the next, the first
+    /// one on ties.
+    pub fn longest_cascade(&self) -> Vec<usize> {
+        let cascade = self.cascade();
+        let Some((mut card, _)) = cascade
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, step)| step.depth)
+        else {
+            return Vec::new();
+        };
+        let mut chain = vec![card];
+        while let Some(previous) = cascade[card].previous {
+            chain.push(previous);
+            card = previous;
+        }
+        chain.reverse();
+        chain
+    }
+}
+
+/// How one card of a [`Pile`] got its copies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cascade {
+    pub matches: usize,
+    pub copies: u64,
+    /// Earlier cards that won copies of this one.
+    pub won_from: Vec<usize>,
+    /// Cards in the longest chain of wins ending here, 1 for a card nothing
+    /// won.
+    pub depth: usize,
+    /// The card before this one in that chain.
+    pub previous: Option<usize>,
+}
+
+/// The pile of scratchcards.
+pub struct Puzzle {
+    cards: Vec<Scratchcard>,
+}
+
+impl Puzzle {
+    pub fn cards(&self) -> &[Scratchcard] {
+        &self.cards
+    }
+
+    /// Number of winning numbers we own, per card.
+    pub fn matches(&self) -> impl Iterator<Item = usize> + '_ {
+        self.cards.iter().map(Scratchcard::matches)
+    }
+
+    /// The pile played out, `None` if the copies overflow a `u64`.
+    pub fn pile(&self) -> Option<Pile> {
+        Pile::play(self.matches())
+    }
+}
+
+impl Solution for Puzzle {
+    type Answer1 = u128;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let cards = parse::lines(input)
+            .map(|line| Scratchcard::parse(&line))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { cards })
+    }
+
+    fn part1(&self) -> Result<u128, SolveError> {
+        self.cards
+            .iter()
+            .try_fold(0u128, |total, card| total.checked_add(card.points()?))
+            .ok_or_else(|| SolveError::new("the points overflow a u128"))
+    }
+
+    fn part2(&self) -> Result<u64, SolveError> {
+        let overflow = || SolveError::new("the copies overflow a u64");
+        let pile = self.pile().ok_or_else(overflow)?;
+        log::debug!("copies per card: {:?}", pile.copies());
+        pile.total().ok_or_else(overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part1(), Ok(13));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part2(), Ok(30));
+    }
+
+    #[test]
+    fn matches_per_card() {
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        assert!(puzzle.matches().eq([4, 2, 2, 1, 0, 0]));
+    }
+
+    #[test]
+    fn parses_cards() {
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        let card = &puzzle.cards()[2];
+        assert_eq!(card.id(), 3);
+        assert_eq!(card.winning(), [1, 21, 53, 59, 44]);
+        assert_eq!(card.owned(), [69, 82, 63, 72, 16, 21, 14, 1]);
+        assert!(Puzzle::parse("Crad 1: 1 | 1").is_err());
+        assert!(Puzzle::parse("Card 1: 1 2 3").is_err());
+        let err = Puzzle::parse("Card 1: 1 x | 1").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 11));
+    }
+
+    #[test]
+    fn many_matches() {
+        let card = |matches: u32| {
+            let numbers: Vec<String> = (0..matches).map(|n| n.to_string()).collect();
+            let numbers = numbers.join(" ");
+            format!("Card 1: {} | {}", numbers, numbers)
+        };
+        let puzzle = Puzzle::parse(&card(33)).unwrap();
+        assert_eq!(puzzle.part1(), Ok(1 << 32));
+        let puzzle = Puzzle::parse(&card(100)).unwrap();
+        assert_eq!(puzzle.part1(), Ok(1 << 99));
+        let puzzle = Puzzle::parse(&format!("{}\n{}", card(100), card(65))).unwrap();
+        assert_eq!(puzzle.part1(), Ok((1 << 99) + (1 << 64)));
+
+        // Numbers past the bitset take the `HashSet` path.
+        let puzzle = Puzzle::parse(&card(128)).unwrap();
+        assert_eq!(puzzle.cards()[0].matches(), 128);
+        assert_eq!(puzzle.part1(), Ok(1 << 127));
+        assert!(Puzzle::parse(&card(129)).unwrap().part1().is_err());
+        let puzzle = Puzzle::parse(&format!("{}\n{}", card(128), card(128))).unwrap();
+        assert!(puzzle.part1().is_err());
+    }
+
+    #[test]
+    fn large_numbers() {
+        let puzzle = Puzzle::parse("Card 1: 5 100 4000000000 | 100 6 4000000000 5").unwrap();
+        assert_eq!(puzzle.cards()[0].matches(), 3);
+        assert_eq!(count_matches(&[1, 2, 100], &[100, 2]), 2);
+        assert_eq!(count_matches(&[1, 2, 99], &[99, 2]), 2);
+    }
+
+    #[test]
+    fn bitset_matches_hash_set() {
+        let winning = [0, 1, 41, 63, 64, 98, 99];
+        let owned = [99, 64, 5, 0, 41, 41, 7];
+        assert_eq!(matches(&winning, &owned), 4);
+        assert_eq!(matches_hashed(&winning, &owned), 4);
+
+        let set: NumberSet = owned.into_iter().collect();
+        assert_eq!(set.len(), 6);
+        assert!(set.contains(64) && !set.contains(63) && !set.contains(100));
+    }
+
+    #[test]
+    fn pile_clamps_at_the_end() {
+        // The last cards win copies of cards past the end of the table.
+        let pile = Pile::play([0, 3, 5]).unwrap();
+        assert_eq!(pile.copies(), [1, 1, 2]);
+        assert_eq!(pile.total(), Some(4));
+
+        // More than the 300 cards a fixed table would hold.
+        let pile = Pile::play(std::iter::repeat_n(1, 1000)).unwrap();
+        assert_eq!(pile.copies()[999], 1000);
+        assert_eq!(pile.total(), Some(500_500));
+    }
+
+    #[test]
+    fn pile_overflow() {
+        // Copies roughly double with every card.
+        assert!(Pile::play(std::iter::repeat_n(10, 80)).is_none());
+        let mut pile = Pile::new();
+        while pile.add(10).is_some() {}
+        let copies = pile.copies().to_vec();
+        assert!(copies.len() < 80);
+        assert_eq!(pile.add(10), None);
+        assert_eq!(pile.copies(), copies);
+
+        let cards: Vec<String> = (1..=80)
+            .map(|id| format!("Card {}: 0 1 2 3 4 5 6 7 8 9 | 0 1 2 3 4 5 6 7 8 9", id))
+            .collect();
+        let puzzle = Puzzle::parse(&cards.join("\n")).unwrap();
+        assert!(puzzle.part2().is_err());
+    }
+
+    #[test]
+    fn cascade_stats() {
+        let pile = Puzzle::parse(EXAMPLE).unwrap().pile().unwrap();
+        let cascade = pile.cascade();
+        assert_eq!(cascade[3].won_from, [0, 1, 2]);
+        assert_eq!(cascade[4].won_from, [0, 2, 3]);
+        assert!(cascade[5].won_from.is_empty());
+        assert_eq!(pile.most_copied(), Some((4, 14)));
+        assert_eq!(pile.longest_cascade(), [0, 1, 2, 3, 4]);
+
+        assert_eq!(Pile::new().most_copied(), None);
+        assert!(Pile::new().longest_cascade().is_empty());
+    }
+}
@@ -0,0 +1,461 @@
+//! Day 5: If You Give A Seed A Fertilizer
+
+use std::{fmt, ops::Range};
+
+use aoc_common::{
+    parse::{self, Line},
+    ParseError, Solution, SolveError,
+};
+
+/// One `destination source length` line of an almanac map.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Encoder {
+    in_start: u64,
+    out_start: u64,
+    range: u64,
+}
+
+impl Encoder {
+    /// Maps `range` values from `in_start` on to `out_start` on, panics when
+    /// either end does not fit a `u64`.
+    pub fn new(in_start: u64, out_start: u64, range: u64) -> Self {
+        assert!(
+            in_start.checked_add(range).is_some() && out_start.checked_add(range).is_some(),
+            "encoder range overflows"
+        );
+        Self {
+            in_start,
+            out_start,
+            range,
+        }
+    }
+
+    pub fn encode(&self, value: u64) -> Option<u64> {
+        if value >= self.in_start && value < self.in_start + self.range {
+            Some(self.out_start + (value - self.in_start))
+        } else {
+            None
+        }
+    }
+
+    /// Values this encoder maps.
+    pub fn source(&self) -> Range<u64> {
+        self.in_start..self.in_start + self.range
+    }
+
+    /// Splits `range` into the part inside [`Encoder::source`], mapped, and
+    /// the parts before and after it, left as they are.
+    pub fn encode_range(&self, range: Range<u64>) -> (Option<Range<u64>>, [Range<u64>; 2]) {
+        let source = self.source();
+        let start = range.start.max(source.start);
+        let end = range.end.min(source.end);
+        let before = range.start..range.end.min(source.start);
+        let after = range.start.max(source.end)..range.end;
+        let inside = (start < end).then(|| {
+            self.out_start + (start - self.in_start)..self.out_start + (end - self.in_start)
+        });
+        (inside, [before, after])
+    }
+
+    /// The part of this encoder whose outputs `then` maps, encoding through
+    /// both in one step.
+    pub fn combine(&self, then: &Self) -> Option<Encoder> {
+        let start = self.out_start.max(then.in_start);
+        let end = (self.out_start + self.range).min(then.in_start + then.range);
+        (start < end).then(|| {
+            Encoder::new(
+                self.in_start + (start - self.out_start),
+                then.out_start + (start - then.in_start),
+                end - start,
+            )
+        })
+    }
+
+    fn identity(range: Range<u64>) -> Self {
+        Self::new(range.start, range.start, range.end - range.start)
+    }
+
+    fn is_identity(&self) -> bool {
+        self.in_start == self.out_start
+    }
+}
+
+/// One almanac map, e.g. `seed-to-soil`. Values no encoder maps keep their
+/// number.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EncoderMap {
+    map: Vec<Encoder>,
+}
+
+impl EncoderMap {
+    pub fn encoders(&self) -> &[Encoder] {
+        &self.map
+    }
+
+    /// The map applying `self` and then `next`, as a single list of
+    /// encoders sorted by source.
+    pub fn then(&self, next: &EncoderMap) -> EncoderMap {
+        let next = next.filled();
+        let mut map: Vec<Encoder> = Vec::new();
+        for enc in self.filled() {
+            for then in next.iter() {
+                let Some(combined) = enc.combine(then) else {
+                    continue;
+                };
+                match map.last_mut() {
+                    Some(last)
+                        if last.in_start + last.range == combined.in_start
+                            && last.out_start + last.range == combined.out_start =>
+                    {
+                        last.range += combined.range
+                    }
+                    _ => map.push(combined),
+                }
+            }
+        }
+        map.retain(|enc| !enc.is_identity());
+        EncoderMap { map }
+    }
+
+    /// The encoders sorted by source, with the values between them (up to
+    /// `u64::MAX`) covered by encoders keeping their number.
+    fn filled(&self) -> Vec<Encoder> {
+        let mut sorted = self.map.clone();
+        sorted.sort_by_key(|enc| enc.in_start);
+        let mut filled = Vec::with_capacity(2 * sorted.len() + 1);
+        let mut covered = 0;
+        for enc in sorted {
+            if covered < enc.in_start {
+                filled.push(Encoder::identity(covered..enc.in_start));
+            }
+            covered = covered.max(enc.source().end);
+            filled.push(enc);
+        }
+        if covered < u64::MAX {
+            filled.push(Encoder::identity(covered..u64::MAX));
+        }
+        filled
+    }
+
+    /// Adds a `destination source length` line.
+    pub fn add_line(&mut self, line: &Line) -> Result<(), ParseError> {
+        let mut tokens = line.text.split_whitespace();
+        let mut next = |what: &str| -> Result<(&str, u64), ParseError> {
+            let token = tokens
+                .next()
+                .ok_or_else(|| line.missing(line.text, format!("expected {}", what)))?;
+            Ok((token, line.parse(token, what)?))
+        };
+        let (_, out_start) = next("a destination range start")?;
+        let (_, in_start) = next("a source range start")?;
+        let (token, range) = next("a range length")?;
+        if let Some(extra) = tokens.next() {
+            return Err(line.error(extra, "expected end of line"));
+        }
+        if in_start.max(out_start).checked_add(range).is_none() {
+            return Err(line.error(token, "expected ranges ending below 2^64"));
+        }
+        self.map.push(Encoder::new(in_start, out_start, range));
+        Ok(())
+    }
+
+    /// Maps whole `ranges` at once, splitting them where encoders start or
+    /// end. The result is in no particular order.
+    pub fn encode_ranges(&self, ranges: impl IntoIterator<Item = Range<u64>>) -> Vec<Range<u64>> {
+        let mut pending: Vec<Range<u64>> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+        let mut encoded = Vec::new();
+        for enc in self.map.iter() {
+            let mut rest = Vec::new();
+            for range in pending {
+                let (inside, outside) = enc.encode_range(range);
+                encoded.extend(inside);
+                rest.extend(outside.into_iter().filter(|r| !r.is_empty()));
+            }
+            pending = rest;
+        }
+        encoded.append(&mut pending);
+        encoded
+    }
+
+    pub fn encode_value(&self, value: u64) -> u64 {
+        self.map
+            .iter()
+            .find_map(|enc| enc.encode(value))
+            .unwrap_or(value)
+    }
+
+    pub fn encode(&self, values: &mut [u64]) {
+        for value in values {
+            *value = self.encode_value(*value);
+        }
+    }
+}
+
+/// Lists the encoders as `destination source length` lines, like the
+/// almanac does.
+impl fmt::Display for EncoderMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for enc in self.map.iter() {
+            writeln!(f, "{} {} {}", enc.out_start, enc.in_start, enc.range)?;
+        }
+        Ok(())
+    }
+}
+
+/// The seeds and the chain of maps from seed to location.
+pub struct Puzzle {
+    seeds: Vec<u64>,
+    maps: Vec<EncoderMap>,
+}
+
+impl Puzzle {
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    pub fn maps(&self) -> &[EncoderMap] {
+        &self.maps
+    }
+
+    /// The seeds line read as `start length` pairs.
+    pub fn seed_ranges(&self) -> impl Iterator<Item = Range<u64>> + '_ {
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+    }
+
+    /// The whole chain of maps composed into a single seed-to-location map.
+    pub fn almanac(&self) -> EncoderMap {
+        self.maps
+            .iter()
+            .fold(EncoderMap::default(), |almanac, map| almanac.then(map))
+    }
+
+    fn lowest_location(&self, mut values: Vec<u64>) -> Option<u64> {
+        for encoder_map in self.maps.iter() {
+            encoder_map.encode(values.as_mut_slice());
+        }
+        values.into_iter().min()
+    }
+}
+
+impl Solution for Puzzle {
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = parse::lines(input);
+
+        let line = lines.expect("the seeds")?;
+        let (_, seeds) = line.split_once(line.text, ": ")?;
+        let tokens: Vec<&str> = seeds.split_whitespace().collect();
+        if tokens.is_empty() || !tokens.len().is_multiple_of(2) {
+            return Err(line.error(seeds.trim(), "expected `start length` seed pairs"));
+        }
+        let mut seeds: Vec<u64> = Vec::with_capacity(tokens.len());
+        for pair in tokens.chunks_exact(2) {
+            let start: u64 = line.parse(pair[0], "a seed number")?;
+            let length: u64 = line.parse(pair[1], "a seed range length")?;
+            if length == 0 {
+                return Err(line.error(pair[1], "expected a non-empty seed range"));
+            }
+            if start.checked_add(length).is_none() {
+                return Err(line.error(pair[1], "expected a seed range ending below 2^64"));
+            }
+            seeds.extend([start, length]);
+        }
+
+        let mut maps: Vec<EncoderMap> = Vec::new();
+        for line in lines {
+            if let Some(first) = line.text.chars().next() {
+                if first.is_alphabetic() {
+                    maps.push(EncoderMap::default());
+                } else if let Some(encoder_map) = maps.last_mut() {
+                    encoder_map.add_line(&line)?;
+                } else {
+                    return Err(line.error(line.text, "expected a map header"));
+                }
+            }
+        }
+        Ok(Self { seeds, maps })
+    }
+
+    fn part1(&self) -> Result<u64, SolveError> {
+        self.lowest_location(self.seeds.clone())
+            .ok_or_else(|| SolveError::new("there are no seeds"))
+    }
+
+    fn part2(&self) -> Result<u64, SolveError> {
+        let mut ranges: Vec<Range<u64>> = self.seed_ranges().collect();
+        log::debug!("seed ranges {:?}", ranges);
+        for encoder_map in self.maps.iter() {
+            ranges = encoder_map.encode_ranges(ranges);
+        }
+        ranges
+            .iter()
+            .map(|range| range.start)
+            .min()
+            .ok_or_else(|| SolveError::new("every seed range is empty"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part1(), Ok(35));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part2(), Ok(46));
+    }
+
+    #[test]
+    fn seed_to_soil() {
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        let mut values = [79, 14, 55, 13];
+        puzzle.maps()[0].encode(&mut values);
+        assert_eq!(values, [81, 14, 57, 13]);
+    }
+
+    #[test]
+    fn split_ranges() {
+        let enc = Encoder::new(10, 100, 5);
+        assert_eq!(enc.encode_range(0..20), (Some(100..105), [0..10, 15..20]));
+        assert_eq!(enc.encode_range(12..13).0, Some(102..103));
+        assert_eq!(enc.encode_range(0..10).0, None);
+        assert_eq!(enc.encode_range(15..30).0, None);
+
+        // Seed range 79..93 lies inside `52 50 48`.
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        let soil = puzzle.maps()[0].encode_ranges(puzzle.seed_ranges());
+        assert_eq!(soil, [81..95, 57..70]);
+        let mut split = puzzle.maps()[0].encode_ranges(Some(40..100));
+        split.sort_by_key(|r| r.start);
+        assert_eq!(split, [40..50, 50..52, 52..100]);
+    }
+
+    #[test]
+    fn combine_encoders() {
+        let enc = Encoder::new(10, 100, 5);
+        assert_eq!(
+            enc.combine(&Encoder::new(102, 0, 10)),
+            Some(Encoder::new(12, 0, 3))
+        );
+        assert_eq!(
+            enc.combine(&Encoder::new(90, 50, 12)),
+            Some(Encoder::new(10, 60, 2))
+        );
+        assert_eq!(enc.combine(&Encoder::new(105, 0, 10)), None);
+    }
+
+    #[test]
+    fn composed_almanac() {
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        let almanac = puzzle.almanac();
+        let locations: Vec<u64> = puzzle
+            .seeds()
+            .iter()
+            .map(|&seed| almanac.encode_value(seed))
+            .collect();
+        assert_eq!(locations, [82, 43, 86, 35]);
+        let lowest = almanac
+            .encode_ranges(puzzle.seed_ranges())
+            .iter()
+            .map(|range| range.start)
+            .min();
+        assert_eq!(lowest, Some(46));
+
+        // Every value maps like it does through the chain of maps.
+        for seed in 0..120 {
+            let mut value = [seed];
+            for map in puzzle.maps() {
+                map.encode(&mut value);
+            }
+            assert_eq!(almanac.encode_value(seed), value[0], "seed {}", seed);
+        }
+
+        let sources: Vec<u64> = almanac
+            .encoders()
+            .iter()
+            .map(|enc| enc.source().start)
+            .collect();
+        assert!(sources.windows(2).all(|pair| pair[0] < pair[1]));
+        let table = almanac.to_string();
+        assert!(
+            table.starts_with("22 0 14\n43 14 1\n36 15 7\n"),
+            "{}",
+            table
+        );
+        assert!(table.ends_with("67 98 1\n19 99 1\n"), "{}", table);
+    }
+
+    #[test]
+    fn seed_pairs() {
+        let maps = EXAMPLE.split_once('\n').unwrap().1;
+        let parse = |seeds: &str| Puzzle::parse(&format!("{}\n{}", seeds, maps));
+        for seeds in ["seeds: 79", "seeds: 79 14 55", "seeds: "] {
+            let err = parse(seeds).err().unwrap();
+            assert_eq!((err.line, err.column), (1, 8), "{}", seeds);
+        }
+        let err = parse("seeds: 18446744073709551615 2").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 29));
+
+        let err = parse("seeds: 79 0").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 11));
+    }
+
+    #[test]
+    fn overflowing_ranges() {
+        let add = |text| EncoderMap::default().add_line(&Line::new(1, text));
+        let err = add("0 18446744073709551615 1").unwrap_err();
+        assert_eq!(err.column, 24);
+        let err = add("18446744073709551615 0 2").unwrap_err();
+        assert_eq!(err.column, 24);
+
+        let mut map = EncoderMap::default();
+        map.add_line(&Line::new(1, "0 18446744073709551614 1"))
+            .unwrap();
+        assert_eq!(map.encode_value(u64::MAX - 1), 0);
+        assert_eq!(map.encode_value(u64::MAX), u64::MAX);
+        let almanac = EncoderMap::default().then(&map);
+        assert_eq!(almanac.encode_value(u64::MAX - 1), 0);
+    }
+}
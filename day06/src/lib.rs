@@ -0,0 +1,150 @@
+//! Day 6: Wait For It
+
+use aoc_common::{
+    parse::{self, Line},
+    ParseError, Solution, SolveError,
+};
+
+/// The race sheet: times and record distances.
+pub struct Puzzle {
+    times: Vec<u32>,
+    distances: Vec<u32>,
+    /// The single race obtained by ignoring the spaces between the numbers.
+    time: u64,
+    distance: u64,
+}
+
+/// Parses `Label: n n n` returning the numbers and the single number read
+/// with the spaces removed.
+fn parse_numbers(line: &Line, what: &str) -> Result<(Vec<u32>, u64), ParseError> {
+    let (_, chunk) = line.split_once(line.text, ":")?;
+    let numbers = line.numbers(chunk, what)?;
+    let mut string = String::from(chunk);
+    string.retain(|c| !c.is_whitespace());
+    let kerned = string
+        .parse::<u64>()
+        .map_err(|_| line.error(chunk.trim(), format!("expected {} without spaces", what)))?;
+    Ok((numbers, kerned))
+}
+
+/// Number of ways to hold the button that beat `record` in a race of `time`,
+/// 0 if the record cannot be beaten.
+fn ways(time: u64, record: u64) -> u64 {
+    // Holding for `hold` travels `hold * (time - hold)`, more than `record`
+    // strictly between the roots of `hold^2 - time * hold + record`. The
+    // integer square root lands next to the lower root, the loops settle it
+    // on the first winning hold.
+    let (time, record) = (u128::from(time), u128::from(record));
+    let travels = |hold: u128| hold * (time - hold);
+    let Some(discriminant) = (time * time).checked_sub(4 * record) else {
+        return 0;
+    };
+    let mut lowest = (time - discriminant.isqrt()) / 2;
+    while lowest <= time / 2 && travels(lowest) <= record {
+        lowest += 1;
+    }
+    while lowest > 0 && travels(lowest - 1) > record {
+        lowest -= 1;
+    }
+    // Winning holds are symmetric around `time / 2`.
+    (time + 1).saturating_sub(2 * lowest) as u64
+}
+
+impl Solution for Puzzle {
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = parse::lines(input);
+        let times_line = lines.expect("the race times")?;
+        let distances_line = lines.expect("the record distances")?;
+
+        let (times, time) = parse_numbers(&times_line, "a race time")?;
+        let (distances, distance) = parse_numbers(&distances_line, "a record distance")?;
+        if times.len() != distances.len() {
+            let message = format!(
+                "expected {} distances, found {}",
+                times.len(),
+                distances.len()
+            );
+            return Err(distances_line.error(distances_line.text, message));
+        }
+
+        Ok(Self {
+            times,
+            distances,
+            time,
+            distance,
+        })
+    }
+
+    fn part1(&self) -> Result<u64, SolveError> {
+        let mut product: u64 = 1;
+        for (i, (&time, &distance)) in self.times.iter().zip(&self.distances).enumerate() {
+            let count = ways(time.into(), distance.into());
+            log::debug!("{}: {}", i, count);
+            if count == 0 {
+                let message = format!("the record of race {} cannot be beaten", i + 1);
+                return Err(SolveError::new(message));
+            }
+            product = product
+                .checked_mul(count)
+                .ok_or_else(|| SolveError::new("the product of the ways to win overflows"))?;
+        }
+        Ok(product)
+    }
+
+    fn part2(&self) -> Result<u64, SolveError> {
+        match ways(self.time, self.distance) {
+            0 => Err(SolveError::new("the record cannot be beaten")),
+            count => Ok(count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part1(), Ok(288));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part2(), Ok(71503));
+    }
+
+    #[test]
+    fn unbeatable_record() {
+        let puzzle = Puzzle::parse("Time: 7 5\nDistance: 9 6").unwrap();
+        let err = puzzle.part1().unwrap_err();
+        assert_eq!(err.message, "the record of race 2 cannot be beaten");
+        assert_eq!(ways(5, 6), 0);
+        assert_eq!(ways(30, 200), 9);
+        assert!(Puzzle::parse("Time: 5\nDistance: 6")
+            .unwrap()
+            .part2()
+            .is_err());
+    }
+
+    #[test]
+    fn exact_roots() {
+        // Holding 10 or 20 only ties the record.
+        let puzzle = Puzzle::parse("Time: 30\nDistance: 200").unwrap();
+        assert_eq!(puzzle.part1(), Ok(9));
+        assert_eq!(puzzle.part2(), Ok(9));
+        for time in 0..40 {
+            for record in 0..400 {
+                let brute = (0..=time)
+                    .filter(|hold| hold * (time - hold) > record)
+                    .count();
+                assert_eq!(ways(time, record), brute as u64, "{} {}", time, record);
+            }
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Day 7: Camel Cards
+
+use aoc_common::{parse, ParseError, Solution, SolveError};
+
+/// Type of a hand, from the weakest to the strongest.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandKind {
+    HighCard,
+    OnePair,
+    TwoPairs,
+    Triple,
+    FullHouse,
+    Quad,
+    Penta,
+}
+
+impl From<&[u8; 5]> for HandKind {
+    fn from(value: &[u8; 5]) -> Self {
+        match value {
+            [5, 5, 5, 5, 5] => Self::Penta,
+            [4, 4, 4, 4, 1] => Self::Quad,
+            [3, 3, 3, 2, 2] => Self::FullHouse,
+            [3, 3, 3, 1, 1] => Self::Triple,
+            [2, 2, 2, 2, 1] => Self::TwoPairs,
+            [2, 2, 1, 1, 1] => Self::OnePair,
+            [1, 1, 1, 1, 1] => Self::HighCard,
+            _ => panic!("unknown hand"),
+        }
+    }
+}
+
+impl From<&([u8; 5], u8)> for HandKind {
+    fn from(value: &([u8; 5], u8)) -> Self {
+        let values = value.0;
+        let jokers = value.1;
+        match jokers {
+            5 | 4 => Self::Penta,
+            3 => {
+                if values[4] == 2 {
+                    Self::Penta
+                } else {
+                    Self::Quad
+                }
+            }
+            2 => match values[2] {
+                3 => Self::Penta,
+                2 => Self::Quad,
+                1 => Self::Triple,
+                _ => panic!("2 jokers promo fail"),
+            },
+            1 => match values[0] {
+                4 => Self::Penta,
+                3 => Self::Quad,
+                2 => {
+                    if values[2] == 2 {
+                        Self::FullHouse
+                    } else {
+                        Self::Triple
+                    }
+                }
+                1 => Self::OnePair,
+                _ => panic!("1 joker promo fail"),
+            },
+            0 => Self::from(&values),
+            _ => panic!("too many jokers"),
+        }
+    }
+}
+
+/// Five cards with their bet, ordered by kind and then card by card.
+#[derive(Debug, Eq)]
+pub struct Hand {
+    values: [u8; 5],
+    kind: HandKind,
+    bet: u32,
+}
+
+impl Hand {
+    pub fn new(values: [u8; 5], bet: u32) -> Self {
+        let mut counts: [u8; 5] = [0; 5];
+        for i in 0..5 {
+            let val = values[i];
+            counts[i] = values
+                .iter()
+                .fold(0, |acc, x| if *x == val { acc + 1 } else { acc });
+        }
+        counts.sort();
+        counts.reverse();
+        Self {
+            values,
+            kind: HandKind::from(&counts),
+            bet,
+        }
+    }
+
+    /// Same as [`Hand::new`] with cards of value `1` acting as jokers.
+    pub fn new2(values: [u8; 5], bet: u32) -> Self {
+        let mut counts: [u8; 5] = [0; 5];
+        for i in 0..5 {
+            let val = values[i];
+            counts[i] = values
+                .iter()
+                .fold(0, |acc, x| if *x == val { acc + 1 } else { acc });
+        }
+        let jokers: u8 = values
+            .iter()
+            .fold(0, |acc, x| if *x == 1 { acc + 1 } else { acc });
+        counts.sort();
+        counts.reverse();
+        Self {
+            values,
+            kind: HandKind::from(&(counts, jokers)),
+            bet,
+        }
+    }
+
+    pub fn values(&self) -> [u8; 5] {
+        self.values
+    }
+
+    pub fn kind(&self) -> &HandKind {
+        &self.kind
+    }
+
+    pub fn bet(&self) -> u32 {
+        self.bet
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.values == other.values
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        if self.kind == other.kind {
+            self.values.cmp(&other.values)
+        } else {
+            self.kind.cmp(&other.kind)
+        }
+    }
+}
+
+/// Strength of a card, from `1` for `2` up to `13` for `A`, `None` for
+/// chars that are not cards.
+pub fn card_value(c: u8) -> Option<u8> {
+    match c {
+        b'A' => Some(13),
+        b'K' => Some(12),
+        b'Q' => Some(11),
+        b'J' => Some(10),
+        b'T' => Some(9),
+        b'9' => Some(8),
+        b'8' => Some(7),
+        b'7' => Some(6),
+        b'6' => Some(5),
+        b'5' => Some(4),
+        b'4' => Some(3),
+        b'3' => Some(2),
+        b'2' => Some(1),
+        _ => None,
+    }
+}
+
+/// Card values with `J` as the weakest card, a joker.
+pub fn joker_card_value(c: u8) -> Option<u8> {
+    match c {
+        b'A' => Some(13),
+        b'K' => Some(12),
+        b'Q' => Some(11),
+        b'T' => Some(10),
+        b'9' => Some(9),
+        b'8' => Some(8),
+        b'7' => Some(7),
+        b'6' => Some(6),
+        b'5' => Some(5),
+        b'4' => Some(4),
+        b'3' => Some(3),
+        b'2' => Some(2),
+        b'J' => Some(1),
+        _ => None,
+    }
+}
+
+fn total_winnings(mut hands: Vec<Hand>) -> u32 {
+    hands.sort();
+    hands
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, x)| acc + (i as u32 + 1) * x.bet)
+}
+
+/// The hands as dealt, with their bets.
+pub struct Puzzle {
+    /// Raw cards of every hand with its bet.
+    hands: Vec<([u8; 5], u32)>,
+}
+
+impl Solution for Puzzle {
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut hands = Vec::new();
+        for line in parse::lines(input) {
+            let (cards, bet) = line.split_once(line.text, " ")?;
+            for (i, c) in cards.char_indices() {
+                if !c.is_ascii() || card_value(c as u8).is_none() {
+                    let token = &cards[i..i + c.len_utf8()];
+                    return Err(line.error(token, "expected a card, one of `AKQJT98765432`"));
+                }
+            }
+            let cards: [u8; 5] = cards
+                .as_bytes()
+                .try_into()
+                .map_err(|_| line.error(cards, "expected a hand of 5 cards"))?;
+            let bet: u32 = line.parse(bet, "a bet")?;
+            hands.push((cards, bet));
+        }
+        Ok(Self { hands })
+    }
+
+    fn part1(&self) -> Result<u32, SolveError> {
+        let hands = self
+            .hands
+            .iter()
+            .map(|(cards, bet)| {
+                Hand::new(
+                    cards.map(|c| card_value(c).expect("validated by parse")),
+                    *bet,
+                )
+            })
+            .collect();
+        Ok(total_winnings(hands))
+    }
+
+    fn part2(&self) -> Result<u32, SolveError> {
+        let hands = self
+            .hands
+            .iter()
+            .map(|(cards, bet)| {
+                let hand = Hand::new2(
+                    cards.map(|c| joker_card_value(c).expect("validated by parse")),
+                    *bet,
+                );
+                if cards.contains(&b'J') {
+                    log::trace!("{}: {:?}", String::from_utf8_lossy(cards), hand);
+                }
+                hand
+            })
+            .collect();
+        Ok(total_winnings(hands))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    fn hand(cards: &str) -> Hand {
+        let values: [u8; 5] = cards.as_bytes().try_into().unwrap();
+        Hand::new(values.map(|c| card_value(c).unwrap()), 0)
+    }
+
+    fn joker_hand(cards: &str) -> Hand {
+        let values: [u8; 5] = cards.as_bytes().try_into().unwrap();
+        Hand::new2(values.map(|c| joker_card_value(c).unwrap()), 0)
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part1(), Ok(6440));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part2(), Ok(5905));
+    }
+
+    #[test]
+    fn hand_kinds() {
+        assert_eq!(hand("32T3K").kind(), &HandKind::OnePair);
+        assert_eq!(hand("KK677").kind(), &HandKind::TwoPairs);
+        assert_eq!(hand("T55J5").kind(), &HandKind::Triple);
+        assert_eq!(hand("23332").kind(), &HandKind::FullHouse);
+        assert_eq!(hand("AA8AA").kind(), &HandKind::Quad);
+        assert_eq!(hand("23456").kind(), &HandKind::HighCard);
+        assert!(hand("33332") > hand("2AAAA"));
+    }
+
+    #[test]
+    fn joker_promotions() {
+        assert_eq!(joker_hand("T55J5").kind(), &HandKind::Quad);
+        assert_eq!(joker_hand("KTJJT").kind(), &HandKind::Quad);
+        assert_eq!(joker_hand("QQQJA").kind(), &HandKind::Quad);
+        assert_eq!(joker_hand("JJJJJ").kind(), &HandKind::Penta);
+        assert_eq!(joker_hand("2233J").kind(), &HandKind::FullHouse);
+        assert_eq!(joker_hand("2345J").kind(), &HandKind::OnePair);
+        assert_eq!(joker_hand("JJ234").kind(), &HandKind::Triple);
+        assert!(joker_hand("JKKK2") < joker_hand("QQQQ2"));
+    }
+}
@@ -0,0 +1,193 @@
+//! Day 8: Haunted Wasteland
+
+use std::collections::HashMap;
+
+use aoc_common::{
+    parse::{self, Line},
+    ParseError, Solution, SolveError,
+};
+use num::integer::gcd;
+
+/// Three letter node name.
+pub type Node = [u8; 3];
+
+fn parse_node(line: &Line, token: &str) -> Result<Node, ParseError> {
+    token
+        .as_bytes()
+        .try_into()
+        .map_err(|_| line.error(token, "expected a three letter node"))
+}
+
+/// The left/right instructions and the network of nodes.
+pub struct Puzzle {
+    instructions: String,
+    map: HashMap<Node, (Node, Node)>,
+    start_nodes: Vec<Node>,
+}
+
+impl Puzzle {
+    /// Number of steps following the instructions from `start` until `is_end`
+    /// matches, `None` if `start` is not a node or no end is ever reached.
+    pub fn steps(&self, start: Node, is_end: impl Fn(&Node) -> bool) -> Option<u64> {
+        let mut current = start;
+        let mut total: u64 = 0;
+        // Past this many steps some (node, instruction) pair has repeated,
+        // so the walk loops without ending.
+        let limit = (self.map.len() * self.instructions.len()) as u64;
+        let mut inst_cycle = self.instructions.chars().cycle();
+        while !is_end(&current) {
+            if total >= limit {
+                return None;
+            }
+            total += 1;
+
+            let dir = inst_cycle.next()?;
+            let vals = self.map.get(&current)?;
+            if dir == 'R' {
+                current = vals.1;
+            } else {
+                current = vals.0;
+            }
+        }
+        Some(total)
+    }
+}
+
+impl Solution for Puzzle {
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = parse::lines(input);
+
+        let line = lines.expect("the instructions")?;
+        if let Some((i, c)) = line
+            .text
+            .char_indices()
+            .find(|&(_, c)| c != 'L' && c != 'R')
+        {
+            let token = &line.text[i..i + c.len_utf8()];
+            return Err(line.error(token, "expected `L` or `R`"));
+        }
+        if line.text.is_empty() {
+            return Err(line.missing(line.text, "expected `L` or `R`"));
+        }
+        let instructions = String::from(line.text);
+        let _ = lines.expect("an empty line")?;
+        let mut map: HashMap<Node, (Node, Node)> = HashMap::new();
+        let mut start_nodes: Vec<Node> = Vec::new();
+        let mut targets = Vec::new();
+        for line in lines {
+            let (key, ways) = line.split_once(line.text, " = ")?;
+            let key = parse_node(&line, key)?;
+            if key[2] == 65 {
+                start_nodes.push(key);
+            }
+            let ways = ways
+                .strip_prefix('(')
+                .and_then(|ways| ways.strip_suffix(')'))
+                .ok_or_else(|| line.error(ways, "expected `(<left>, <right>)`"))?;
+            let (left, right) = line.split_once(ways, ", ")?;
+            targets.extend([(line, left), (line, right)]);
+            let left = parse_node(&line, left)?;
+            let right = parse_node(&line, right)?;
+            let _ = map.insert(key, (left, right));
+        }
+        for (line, target) in targets {
+            if !map.contains_key(target.as_bytes()) {
+                return Err(line.error(target, "expected a node of the network"));
+            }
+        }
+        Ok(Self {
+            instructions,
+            map,
+            start_nodes,
+        })
+    }
+
+    fn part1(&self) -> Result<u64, SolveError> {
+        self.steps(*b"AAA", |node| node == b"ZZZ")
+            .ok_or_else(|| SolveError::new("there is no way from AAA to ZZZ"))
+    }
+
+    fn part2(&self) -> Result<u64, SolveError> {
+        if self.start_nodes.is_empty() {
+            return Err(SolveError::new("there are no start nodes"));
+        }
+        let mut total = 1;
+        for &start_node in self.start_nodes.iter() {
+            let name = String::from_utf8_lossy(&start_node);
+            let steps = self
+                .steps(start_node, |node| node[2] == 90)
+                .ok_or_else(|| {
+                    SolveError::new(format!("there is no way from {} to an end node", name))
+                })?;
+            log::debug!("{}: {} steps", name, steps);
+            total = (total / gcd(total, steps))
+                .checked_mul(steps)
+                .ok_or_else(|| SolveError::new("the steps of every ghost together overflow"))?;
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE_2: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE_3: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(Puzzle::parse(EXAMPLE_1).unwrap().part1(), Ok(2));
+        assert_eq!(Puzzle::parse(EXAMPLE_2).unwrap().part1(), Ok(6));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE_3).unwrap().part2(), Ok(6));
+    }
+
+    #[test]
+    fn missing_nodes() {
+        let err = Puzzle::parse("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)")
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column), (3, 8));
+        assert!(Puzzle::parse("\n\nAAA = (AAA, AAA)").is_err());
+
+        // No AAA, no ..A start node, and a walk that never ends.
+        let puzzle = Puzzle::parse("LR\n\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert!(puzzle.part1().is_err());
+        assert!(puzzle.part2().is_err());
+        let puzzle =
+            Puzzle::parse("L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(puzzle.steps(*b"AAA", |node| node == b"ZZZ"), None);
+        assert!(puzzle.part2().is_err());
+    }
+}
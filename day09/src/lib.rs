@@ -0,0 +1,99 @@
+//! Day 9: Mirage Maintenance
+
+use aoc_common::{parse, ParseError, Solution, SolveError};
+
+/// Extrapolates the value following the history.
+pub fn next_val(vals: &[i32]) -> i32 {
+    let mut new_line: Vec<i32> = Vec::new();
+    for i in 0..vals.len() - 1 {
+        new_line.push(vals[i + 1] - vals[i]);
+    }
+    assert!(new_line.len() == vals.len() - 1);
+    let last: i32 = *vals.iter().last().unwrap();
+    let last_diff = if new_line.iter().all(|&x| x == 0) {
+        0
+    } else {
+        next_val(&new_line)
+    };
+    last + last_diff
+}
+
+/// Extrapolates the value preceding the history.
+pub fn prev_val(vals: &[i32]) -> i32 {
+    let mut new_line: Vec<i32> = Vec::new();
+    for i in 0..vals.len() - 1 {
+        new_line.push(vals[i + 1] - vals[i]);
+    }
+    assert!(new_line.len() == vals.len() - 1);
+    let first: i32 = *vals.iter().next().unwrap();
+    let first_diff = if new_line.iter().all(|&x| x == 0) {
+        0
+    } else {
+        prev_val(&new_line)
+    };
+    first - first_diff
+}
+
+/// The oasis report, one history per line.
+pub struct Puzzle {
+    histories: Vec<Vec<i32>>,
+}
+
+impl Solution for Puzzle {
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut histories = Vec::new();
+        for line in parse::lines(input) {
+            let vals: Vec<i32> = line.numbers(line.text, "a value")?;
+            if vals.is_empty() {
+                return Err(line.error(line.text, "expected at least one value"));
+            }
+            histories.push(vals);
+        }
+        Ok(Self { histories })
+    }
+
+    fn part1(&self) -> Result<i32, SolveError> {
+        let sum = self
+            .histories
+            .iter()
+            .map(|vals| {
+                let next = next_val(vals);
+                log::trace!("{:?} -> {}", vals, next);
+                next
+            })
+            .sum();
+        Ok(sum)
+    }
+
+    fn part2(&self) -> Result<i32, SolveError> {
+        Ok(self.histories.iter().map(|vals| prev_val(vals)).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part1(), Ok(114));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part2(), Ok(2));
+    }
+
+    #[test]
+    fn single_history() {
+        assert_eq!(next_val(&[10, 13, 16, 21, 30, 45]), 68);
+        assert_eq!(prev_val(&[10, 13, 16, 21, 30, 45]), 5);
+    }
+}
@@ -0,0 +1,364 @@
+//! Day 10: Pipe Maze
+
+use std::fmt::Display;
+
+use aoc_common::{
+    geometry::{Direction, Grid, Point},
+    ParseError, Solution, SolveError,
+};
+
+/// A pipe segment given by the two directions it connects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pipe(pub Direction, pub Direction);
+
+impl Pipe {
+    pub const VERT: Self = Self(Direction::North, Direction::South);
+    pub const HORZ: Self = Self(Direction::West, Direction::East);
+    pub const BEND_L: Self = Self(Direction::North, Direction::East);
+    pub const BEND_J: Self = Self(Direction::North, Direction::West);
+    pub const BEND_7: Self = Self(Direction::South, Direction::West);
+    pub const BEND_F: Self = Self(Direction::South, Direction::East);
+    pub const ALL: [Self; 6] = [
+        Self::VERT,
+        Self::HORZ,
+        Self::BEND_L,
+        Self::BEND_J,
+        Self::BEND_7,
+        Self::BEND_F,
+    ];
+
+    pub fn connects(&self, dir: Direction) -> bool {
+        self.0 == dir || self.1 == dir
+    }
+
+    pub fn get_other(&self, dir: Direction) -> Direction {
+        if dir == self.0 {
+            self.1
+        } else {
+            self.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cell {
+    pub kind: Option<Pipe>,
+}
+
+impl Cell {
+    /// Whether the pipe in this cell leads back when entered going `dir`.
+    pub fn has_conn_to_opposite(&self, dir: Direction) -> bool {
+        self.kind.is_some_and(|pipe| pipe.connects(dir.opposite()))
+    }
+}
+
+impl TryFrom<char> for Cell {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        let kind = match value {
+            '-' => Some(Pipe::HORZ),
+            '|' => Some(Pipe::VERT),
+            'L' => Some(Pipe::BEND_L),
+            'J' => Some(Pipe::BEND_J),
+            '7' => Some(Pipe::BEND_7),
+            'F' => Some(Pipe::BEND_F),
+            '.' | 'S' => None,
+            _ => return Err(value),
+        };
+        Ok(Self { kind })
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let repr = match self.kind {
+            Some(Pipe::HORZ) => '-',
+            Some(Pipe::VERT) => '|',
+            Some(Pipe::BEND_L) => '╰',
+            Some(Pipe::BEND_J) => '╯',
+            Some(Pipe::BEND_7) => '╮',
+            Some(Pipe::BEND_F) => '╭',
+            _ => ' ',
+        };
+        write!(f, "{}", repr)
+    }
+}
+
+/// The grid of tiles with the position of the animal.
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+    grid: Grid<Cell>,
+    start: Point<usize>,
+    /// The loop through `start`, checked on parsing.
+    path: Vec<Point<usize>>,
+}
+
+impl Map {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut start = None;
+        let grid = Grid::parse(input, "a pipe, `.` or `S`", |pos, c| {
+            if c == 'S' {
+                start = Some(pos);
+            }
+            Cell::try_from(c).ok()
+        })?;
+        let start = start.ok_or_else(|| ParseError::new(1, 1, "", "expected a start tile `S`"))?;
+        let path = trace_loop(&grid, start).map_err(|(pos, message)| {
+            let token = input
+                .lines()
+                .nth(pos.y)
+                .and_then(|line| line.chars().nth(pos.x));
+            let token = token.map(String::from).unwrap_or_default();
+            ParseError::new(pos.y + 1, pos.x + 1, &token, message)
+        })?;
+        Ok(Self { grid, start, path })
+    }
+
+    pub fn start(&self) -> Point<usize> {
+        self.start
+    }
+
+    pub fn grid(&self) -> &Grid<Cell> {
+        &self.grid
+    }
+
+    pub fn pipe_len(&self) -> usize {
+        self.pipe_path().len()
+    }
+
+    /// The tiles of the loop, starting at the animal.
+    pub fn pipe_path(&self) -> &[Point<usize>] {
+        &self.path
+    }
+
+    pub fn add_start_pipe(&mut self) {
+        let connected: Vec<Direction> = Direction::CARDINAL
+            .into_iter()
+            .filter(|&dir| {
+                self.grid
+                    .neighbours(self.start, &[dir])
+                    .any(|(_, cell)| cell.has_conn_to_opposite(dir))
+            })
+            .collect();
+        let pipe = Pipe::ALL
+            .into_iter()
+            .find(|pipe| connected.iter().all(|&dir| pipe.connects(dir)));
+        self.grid[self.start].kind = pipe;
+    }
+
+    /// Flags the cells enclosed by the pipe loop, row by row, flipping on every
+    /// pipe that crosses the row going north-south.
+    fn enclosed(&self) -> Grid<bool> {
+        let mut enclosed = Grid::filled(self.grid.width(), self.grid.height(), false);
+        for (y, row) in self.grid.rows().enumerate() {
+            let mut inside = false;
+            for (x, cell) in row.iter().enumerate() {
+                match cell.kind {
+                    None => enclosed[Point::new(x, y)] = inside,
+                    Some(pipe) if pipe.connects(Direction::South) => inside = !inside,
+                    _ => {}
+                }
+            }
+        }
+        enclosed
+    }
+
+    pub fn enclosed_tiles(&self) -> usize {
+        self.enclosed().iter().filter(|(_, &inside)| inside).count()
+    }
+
+    /// Removes every pipe that is not part of `path`.
+    pub fn clear_map(&mut self, path: &[Point<usize>]) {
+        let mut on_path = Grid::filled(self.grid.width(), self.grid.height(), false);
+        for &pos in path {
+            on_path[pos] = true;
+        }
+        for (pos, cell) in self.grid.iter_mut() {
+            if !on_path[pos] {
+                cell.kind = None;
+            }
+        }
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let enclosed = self.enclosed();
+        let mut repr: String = String::new();
+        for (pos, cell) in self.grid.iter() {
+            if pos.x == 0 && pos.y != 0 {
+                repr += "\n";
+            }
+            if enclosed[pos] {
+                repr += "░";
+            } else {
+                repr += &format!("{}", cell);
+            }
+        }
+        write!(f, "{}", repr)
+    }
+}
+
+/// Follows the pipes from `start` back to it, or the first tile breaking the
+/// loop with what is wrong there.
+fn trace_loop(
+    grid: &Grid<Cell>,
+    start: Point<usize>,
+) -> Result<Vec<Point<usize>>, (Point<usize>, &'static str)> {
+    let mut path: Vec<Point<usize>> = vec![start];
+    // find first pipe the stupid way
+    let (mut current, mut from_dir) = Direction::CARDINAL
+        .into_iter()
+        .find_map(|dir| {
+            let next = start.step(dir)?;
+            let cell = grid.get(next)?;
+            cell.has_conn_to_opposite(dir)
+                .then(|| (next, dir.opposite()))
+        })
+        .ok_or((start, "expected a pipe next to the start"))?;
+    while current != start {
+        if path.len() > grid.width() * grid.height() {
+            return Err((current, "expected the pipes to lead back to the start"));
+        }
+        path.push(current);
+        let pipe = match grid[current].kind {
+            Some(pipe) if pipe.connects(from_dir) => pipe,
+            _ => return Err((current, "expected a pipe continuing the loop")),
+        };
+        let new_dir = pipe.get_other(from_dir);
+        current = current
+            .step(new_dir)
+            .filter(|&next| grid.contains(next))
+            .ok_or((current, "expected a pipe staying on the map"))?;
+        from_dir = new_dir.opposite();
+    }
+    Ok(path)
+}
+
+/// The pipe maze.
+pub struct Puzzle {
+    map: Map,
+}
+
+impl Puzzle {
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+}
+
+impl Solution for Puzzle {
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            map: Map::parse(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<usize, SolveError> {
+        Ok(self.map.pipe_len() / 2)
+    }
+
+    fn part2(&self) -> Result<usize, SolveError> {
+        let mut map = self.map.clone();
+        map.clear_map(self.map.pipe_path());
+        map.add_start_pipe();
+        log::debug!("loop and enclosed tiles:\n{}", map);
+        Ok(map.enclosed_tiles())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE_LOOP: &str = "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    const COMPLEX_LOOP: &str = "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+
+    const ENCLOSED: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const SQUEEZED: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    const LARGER: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    const JUNK: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(Puzzle::parse(SQUARE_LOOP).unwrap().part1(), Ok(4));
+        assert_eq!(Puzzle::parse(COMPLEX_LOOP).unwrap().part1(), Ok(8));
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(Puzzle::parse(ENCLOSED).unwrap().part2(), Ok(4));
+        assert_eq!(Puzzle::parse(SQUEEZED).unwrap().part2(), Ok(4));
+        assert_eq!(Puzzle::parse(LARGER).unwrap().part2(), Ok(8));
+        assert_eq!(Puzzle::parse(JUNK).unwrap().part2(), Ok(10));
+    }
+
+    #[test]
+    fn unknown_tile() {
+        let err = Puzzle::parse("S-7\n|x|\nL-J").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 2, "x"));
+    }
+
+    #[test]
+    fn broken_loop() {
+        let error = |input| {
+            let err = Puzzle::parse(input).err().unwrap();
+            (err.line, err.column, err.token)
+        };
+        assert_eq!(error("S-.\n..."), (1, 3, String::from(".")));
+        assert_eq!(error("S-|"), (1, 3, String::from("|")));
+        assert_eq!(error("S-7\n..-"), (2, 3, String::from("-")));
+        assert_eq!(error("S..\n..."), (1, 1, String::from("S")));
+        assert_eq!(error("S-\n|."), (1, 2, String::from("-")));
+        assert_eq!(Puzzle::parse("S-7\n|.|\nL-J").unwrap().part1(), Ok(4));
+    }
+}
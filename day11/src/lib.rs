@@ -0,0 +1,133 @@
+//! Day 11: Cosmic Expansion
+
+use aoc_common::{geometry::Point, parse, ParseError, Solution, SolveError};
+use itertools::Itertools;
+
+/// Galaxies of the image and the image size.
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+    pub galaxies: Vec<Point>,
+    pub dimension: Point,
+}
+
+impl Map {
+    pub fn expand(&mut self, coefficient: i32) {
+        let mut empty_rows: Vec<i32> = (0..self.dimension.y).collect();
+        let mut empty_cols: Vec<i32> = (0..self.dimension.x).collect();
+        for galaxy in self.galaxies.iter() {
+            if let Some(i) = empty_rows.iter().position(|&y| y == galaxy.y) {
+                empty_rows.remove(i);
+            }
+            if let Some(i) = empty_cols.iter().position(|&x| x == galaxy.x) {
+                empty_cols.remove(i);
+            }
+        }
+        for galaxy in self.galaxies.iter_mut() {
+            let xpand: i32 = empty_cols
+                .iter()
+                .fold(0, |acc, &x| if galaxy.x > x { acc + 1 } else { acc });
+            galaxy.x += xpand * (coefficient - 1);
+            let ypand: i32 = empty_rows
+                .iter()
+                .fold(0, |acc, &y| if galaxy.y > y { acc + 1 } else { acc });
+            galaxy.y += ypand * (coefficient - 1);
+        }
+        log::debug!(
+            "empty rows {:?}, empty columns {:?}",
+            empty_rows,
+            empty_cols
+        );
+        self.dimension.x += empty_cols.len() as i32;
+        self.dimension.y += empty_rows.len() as i32;
+    }
+
+    pub fn all_manhattans(&self) -> usize {
+        let combs = self.galaxies.clone().into_iter().combinations(2);
+        let mut total: usize = 0;
+        for combo in combs {
+            let manhattan = combo[0].manhattan(combo[1]) as usize;
+            log::trace!("{} -> {}: {}", combo[0], combo[1], manhattan);
+            total += manhattan;
+        }
+        total
+    }
+}
+
+/// The observatory image.
+pub struct Puzzle {
+    map: Map,
+}
+
+impl Puzzle {
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
+    /// Sum of distances between all galaxy pairs after every empty row and
+    /// column grew `coefficient` times.
+    pub fn expanded_distances(&self, coefficient: i32) -> usize {
+        let mut map = self.map.clone();
+        map.expand(coefficient);
+        map.all_manhattans()
+    }
+}
+
+impl Solution for Puzzle {
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut map: Map = Map::default();
+        for (y, line) in parse::lines(input).enumerate() {
+            for (x, (i, c)) in line.text.char_indices().enumerate() {
+                match c {
+                    '#' => map.galaxies.push(Point::new(x as i32, y as i32)),
+                    '.' => {}
+                    _ => {
+                        let token = &line.text[i..i + c.len_utf8()];
+                        return Err(line.error(token, "expected `#` or `.`"));
+                    }
+                }
+                map.dimension.x = map.dimension.x.max(x as i32 + 1);
+                map.dimension.y = map.dimension.y.max(y as i32 + 1);
+            }
+        }
+        Ok(Self { map })
+    }
+
+    fn part1(&self) -> Result<usize, SolveError> {
+        Ok(self.expanded_distances(2))
+    }
+
+    fn part2(&self) -> Result<usize, SolveError> {
+        Ok(self.expanded_distances(1_000_000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Puzzle::parse(EXAMPLE).unwrap().part1(), Ok(374));
+    }
+
+    #[test]
+    fn expansion_factors() {
+        let puzzle = Puzzle::parse(EXAMPLE).unwrap();
+        assert_eq!(puzzle.expanded_distances(10), 1030);
+        assert_eq!(puzzle.expanded_distances(100), 8410);
+    }
+}